@@ -5,6 +5,14 @@ use gstd::{prelude::*, ActorId};
 
 pub struct DaoMetadata;
 
+/// The fungible token metadata of the DAO shares.
+/// The DAO answers the `FTAction::BalanceOf` and `FTAction::TotalSupply` queries
+/// of the fungible token standard with the shares and sends `FTEvent::Transfer`
+/// to the zero address when the shares are minted, burned or transferred.
+pub const SHARES_NAME: &str = "DAO Shares";
+pub const SHARES_SYMBOL: &str = "SHARES";
+pub const SHARES_DECIMALS: u8 = 0;
+
 impl Metadata for DaoMetadata {
     type Init = In<InitDao>;
     type Handle = InOut<DaoAction, DaoEvent>;
@@ -16,8 +24,20 @@ impl Metadata for DaoMetadata {
 
 #[derive(Debug, Default, Clone, Encode, Decode, TypeInfo)]
 pub struct DaoState {
-    pub admin: ActorId,
+    pub admins: Vec<ActorId>,
+    pub admin_threshold: u32,
+    pub admin_operation_lifetime: u64,
+    pub admin_operation_id: u64,
+    pub admin_operations: Vec<(u64, PendingAdminOperation)>,
+    pub paused: bool,
+    /// the proposed admin and the signer it replaces
+    pub pending_admin: Option<(ActorId, ActorId)>,
+    pub roles: Vec<(ActorId, Vec<Role>)>,
+    pub treasurer_spending_limit: u128,
+    pub treasurer_period: u64,
+    pub treasurer_spent: u128,
     pub approved_token_program_id: ActorId,
+    pub membership_nft: Option<ActorId>,
     pub period_duration: u64,
     pub voting_period_length: u64,
     pub grace_period_length: u64,
@@ -25,11 +45,25 @@ pub struct DaoState {
     pub abort_window: u64,
     pub total_shares: u128,
     pub balance: u128,
+    pub value_balance: u128,
     pub members: Vec<(ActorId, Member)>,
     pub member_by_delegate_key: Vec<(ActorId, ActorId)>,
     pub proposal_id: u128,
     pub proposals: Vec<(u128, Proposal)>,
-    pub whitelist: Vec<ActorId>,
+    pub lanes: Vec<(ProposalLane, u128)>,
+    pub comments: Vec<(u128, Vec<ProposalComment>)>,
+    pub last_comment_at: Vec<(ActorId, u64)>,
+    pub whitelist: Vec<(ActorId, Option<u64>)>,
+    pub grants: Vec<(u128, Grant)>,
+    pub budgets: Vec<(ActorId, Budget)>,
+    pub token_balances: Vec<(ActorId, u128)>,
+    pub whitelist_mode: WhitelistMode,
+    pub membership_gate: Option<MembershipGate>,
+    pub share_transfer_policy: ShareTransferPolicy,
+    pub optimistic: Option<OptimisticConfig>,
+    pub conviction: Option<ConvictionConfig>,
+    pub conviction_request_id: u128,
+    pub conviction_requests: Vec<(u128, ConvictionRequest)>,
     pub transaction_id: u64,
     pub transactions: Vec<(u64, Option<DaoAction>)>,
 }
@@ -42,8 +76,51 @@ impl DaoState {
             .any(|(member_account, member)| member_account == account && member.shares != 0)
     }
 
-    pub fn is_in_whitelist(state: <DaoMetadata as Metadata>::State, account: &ActorId) -> bool {
-        state.whitelist.contains(account)
+    /// Checks the whitelist entry of the account against its expiry at `timestamp`
+    /// (the current block timestamp), as the contract does.
+    pub fn is_in_whitelist(
+        state: <DaoMetadata as Metadata>::State,
+        account: &ActorId,
+        timestamp: u64,
+    ) -> bool {
+        state.whitelist.iter().any(|(whitelisted, expires_at)| {
+            whitelisted == account && expires_at.map_or(true, |expires_at| timestamp <= expires_at)
+        })
+    }
+
+    pub fn is_admin(state: <DaoMetadata as Metadata>::State, account: &ActorId) -> bool {
+        state.admins.contains(account)
+    }
+
+    pub fn has_role(
+        state: <DaoMetadata as Metadata>::State,
+        account: &ActorId,
+        role: Role,
+    ) -> bool {
+        state
+            .roles
+            .iter()
+            .any(|(role_account, roles)| role_account == account && roles.contains(&role))
+    }
+
+    pub fn get_budgets(state: <DaoMetadata as Metadata>::State, account: &ActorId) -> Vec<Budget> {
+        state
+            .budgets
+            .into_iter()
+            .filter(|(budget_account, _)| budget_account == account)
+            .map(|(_, budget)| budget)
+            .collect()
+    }
+
+    pub fn get_admin_operation(
+        state: <DaoMetadata as Metadata>::State,
+        id: u64,
+    ) -> Option<PendingAdminOperation> {
+        state
+            .admin_operations
+            .into_iter()
+            .find(|(operation_id, _)| operation_id == &id)
+            .map(|(_, operation)| operation)
     }
 
     pub fn get_proposal_id(state: <DaoMetadata as Metadata>::State) -> u128 {
@@ -65,6 +142,53 @@ impl DaoState {
         }
     }
 
+    pub fn get_proposal_metadata(
+        state: <DaoMetadata as Metadata>::State,
+        id: u128,
+    ) -> Option<ProposalMetadata> {
+        state
+            .proposals
+            .into_iter()
+            .find(|(proposal_id, _)| *proposal_id == id)
+            .map(|(_, proposal)| proposal.metadata)
+    }
+
+    pub fn get_comments(
+        state: <DaoMetadata as Metadata>::State,
+        proposal_id: u128,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ProposalComment> {
+        state
+            .comments
+            .into_iter()
+            .find(|(id, _)| *id == proposal_id)
+            .map(|(_, comments)| {
+                comments
+                    .into_iter()
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_grant(state: <DaoMetadata as Metadata>::State, id: u128) -> Option<Grant> {
+        state
+            .grants
+            .into_iter()
+            .find(|(grant_id, _)| grant_id == &id)
+            .map(|(_, grant)| grant)
+    }
+
+    pub fn balance_of(state: <DaoMetadata as Metadata>::State, account: &ActorId) -> u128 {
+        state
+            .members
+            .iter()
+            .find(|(member_account, _)| member_account == account)
+            .map_or(0, |(_, member)| member.shares)
+    }
+
     pub fn get_member_info(
         state: <DaoMetadata as Metadata>::State,
         account: &ActorId,
@@ -81,6 +205,23 @@ impl DaoState {
     }
 }
 
+/// The maximum length of the proposal title in bytes.
+pub const MAX_TITLE_LENGTH: usize = 128;
+/// The maximum length of the proposal content URI in bytes.
+pub const MAX_URI_LENGTH: usize = 256;
+
+/// The on-chain part of the proposal description.
+/// The full text lives off-chain (e.g. in IPFS) and is verified by its hash.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Decode, Encode, TypeInfo)]
+pub struct ProposalMetadata {
+    /// the short title of at most [`MAX_TITLE_LENGTH`] bytes
+    pub title: String,
+    /// the hash of the full proposal text
+    pub content_hash: [u8; 32],
+    /// where the full proposal text is stored, at most [`MAX_URI_LENGTH`] bytes
+    pub uri: String,
+}
+
 #[derive(Debug, Default, Clone, Decode, Encode, TypeInfo)]
 pub struct Proposal {
     pub proposer: ActorId,
@@ -91,21 +232,595 @@ pub struct Proposal {
     pub quorum: u128,
     pub is_membership_proposal: bool,
     pub amount: u128,
+    pub value_amount: u128,
     pub processed: bool,
     pub passed: bool,
     pub aborted: bool,
+    pub cancelled: bool,
     pub token_tribute: u128,
-    pub details: String,
+    pub value_tribute: u128,
+    pub metadata: ProposalMetadata,
     pub starting_period: u64,
     pub max_total_shares_at_yes_vote: u128,
-    pub votes_by_member: Vec<(ActorId, Vote)>,
+    /// the votes with the optional reasons given by the voters
+    pub votes_by_member: Vec<(ActorId, Vote, Option<VoteReason>)>,
+    pub kind: ProposalKind,
+    /// the previous proposal in the same lane that must be decided first
+    pub previous_in_lane: Option<u128>,
+    /// the time the voting was closed early since its outcome had been decided
+    pub voting_closed_at: Option<u64>,
+    /// the proposal passes unless the NO votes exceed the objection threshold
+    pub optimistic: bool,
+    /// the ballots of the ranked-choice proposal
+    pub ballots: Vec<RankedBallot>,
+    /// the option of the ranked-choice proposal that won the instant runoff
+    pub winning_option: Option<u32>,
+}
+
+impl Proposal {
+    /// The time the voting period ends, the grace period starts after it.
+    pub fn voting_ends_at(&self, voting_period_length: u64) -> u64 {
+        self.voting_closed_at
+            .unwrap_or(self.starting_period + voting_period_length)
+    }
+
+    /// Checks that the remaining votes can't change the outcome of the voting:
+    /// either the YES votes are the majority of all shares and meet the quorum
+    /// or the YES votes can't outweigh the NO votes or meet the quorum anymore.
+    ///
+    /// The outcome is decided against the given total shares, while the proposal is processed
+    /// against the total shares at that time, so the shares issued or ragequitted
+    /// during the grace period can still change it.
+    pub fn is_decided(&self, total_shares: u128) -> bool {
+        if total_shares == 0 {
+            return false;
+        }
+        let meets_quorum = |votes: u128| votes * 10000 / total_shares >= self.quorum;
+        let max_yes_votes = total_shares.saturating_sub(self.no_votes);
+        let passes =
+            self.yes_votes.saturating_mul(2) > total_shares && meets_quorum(self.yes_votes);
+        let fails = max_yes_votes <= self.no_votes || !meets_quorum(max_yes_votes);
+        passes || fails
+    }
+
+    /// The winner of the instant runoff among the options of the ranked-choice proposal.
+    /// The option with the fewest first choices is eliminated until an option
+    /// has the majority of the ballots that rank any of the remaining options.
+    /// Ties are broken in favour of the option listed first.
+    pub fn instant_runoff(&self) -> Option<u32> {
+        let ProposalKind::RankedChoice(options) = &self.kind else {
+            return None;
+        };
+        let mut eliminated = vec![false; options.len()];
+        loop {
+            let mut tallies = vec![0u128; options.len()];
+            for ballot in &self.ballots {
+                let choice = ballot
+                    .ranking
+                    .iter()
+                    .map(|option| *option as usize)
+                    .find(|option| !eliminated[*option]);
+                if let Some(option) = choice {
+                    tallies[option] = tallies[option].saturating_add(ballot.shares);
+                }
+            }
+            let total = tallies
+                .iter()
+                .fold(0, |total: u128, tally| total.saturating_add(*tally));
+            if total == 0 {
+                return None;
+            }
+            let remaining: Vec<usize> = (0..options.len())
+                .filter(|option| !eliminated[*option])
+                .collect();
+            if let Some(winner) = remaining
+                .iter()
+                .find(|option| tallies[**option].saturating_mul(2) > total)
+            {
+                return Some(*winner as u32);
+            }
+            if remaining.len() == 1 {
+                return Some(remaining[0] as u32);
+            }
+            // the last listed option among the ones with the fewest choices is eliminated
+            let loser = remaining
+                .iter()
+                .copied()
+                .min_by(|a, b| tallies[*a].cmp(&tallies[*b]).then(b.cmp(a)))?;
+            eliminated[loser] = true;
+        }
+    }
+
+    /// The action executed if the proposal passes: the action of the governance proposal
+    /// or the action of the winning option of the ranked-choice proposal.
+    pub fn governance_action(&self) -> Option<&GovernanceAction> {
+        match &self.kind {
+            ProposalKind::Governance(action) => Some(action),
+            ProposalKind::RankedChoice(options) => self
+                .instant_runoff()
+                .and_then(|option| options.get(option as usize))
+                .and_then(|option| option.action.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn lane(&self) -> ProposalLane {
+        match self.kind {
+            ProposalKind::Standard if self.is_membership_proposal => ProposalLane::Membership,
+            ProposalKind::Standard => ProposalLane::Funding,
+            ProposalKind::Governance(_) | ProposalKind::RankedChoice(_) => ProposalLane::Governance,
+            ProposalKind::Grant(_) => ProposalLane::Grant,
+            ProposalKind::Trade { .. } => ProposalLane::Trade,
+            ProposalKind::Signaling => ProposalLane::Signaling,
+        }
+    }
+}
+
+/// The independent queue of proposals.
+/// The proposals are started with `period_duration` gaps and processed in order
+/// only within their lane, so a stuck proposal doesn't block the other lanes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Decode, Encode, TypeInfo)]
+pub enum ProposalLane {
+    Membership,
+    Funding,
+    Governance,
+    Grant,
+    Trade,
+    Signaling,
+}
+
+#[derive(Debug, Default, Clone, Decode, Encode, TypeInfo)]
+pub enum ProposalKind {
+    /// The membership or the funding proposal (see `is_membership_proposal`).
+    #[default]
+    Standard,
+    /// The proposal executing the governance action if it passes.
+    Governance(GovernanceAction),
+    /// The funding proposal paying `amount` to the applicant by the schedule.
+    Grant(GrantSchedule),
+    /// The proposal exchanging `token_tribute` of `tribute_token` from the applicant
+    /// for `amount` of `payment_token` and `shares_requested`.
+    Trade {
+        tribute_token: ActorId,
+        payment_token: ActorId,
+    },
+    /// The proposal choosing one of the options by ranked ballots,
+    /// the action of the winning option is executed.
+    RankedChoice(Vec<ProposalOption>),
+    /// The non-binding proposal that only records the opinion of the members.
+    /// Its YES votes don't lock the shares, its text is referenced by the proposal metadata.
+    Signaling,
+}
+
+/// The option of the ranked-choice proposal.
+#[derive(Debug, Clone, Decode, Encode, TypeInfo)]
+pub struct ProposalOption {
+    /// the short description of at most [`MAX_TITLE_LENGTH`] bytes
+    pub description: String,
+    /// the action executed if the option wins
+    pub action: Option<GovernanceAction>,
+}
+
+/// The ballot ranking the options of the ranked-choice proposal from the most preferred.
+#[derive(Debug, Clone, Decode, Encode, TypeInfo)]
+pub struct RankedBallot {
+    pub voter: ActorId,
+    /// the option indices in the order of preference
+    pub ranking: Vec<u32>,
+    /// the shares of the member at the vote
+    pub shares: u128,
+}
+
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub enum GrantSchedule {
+    /// The amount is released linearly.
+    Vesting(VestingSchedule),
+    /// The amount is released milestone by milestone in order.
+    Milestones(Vec<Milestone>),
+    /// The amount is paid out in equal payments every interval.
+    Recurring(RecurringSchedule),
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo)]
+pub struct RecurringSchedule {
+    /// the timestamp when the first payment is due
+    pub start: u64,
+    /// the time between payments
+    pub interval: u64,
+    /// the number of fungible tokens in one payment
+    pub payment: u128,
+    /// when the payments stop
+    pub until: RecurringLimit,
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo)]
+pub enum RecurringLimit {
+    /// the total number of payments
+    Payments(u32),
+    /// the timestamp after which no payments are due
+    EndDate(u64),
+}
+
+impl RecurringSchedule {
+    // the total number of payments of the schedule, `None` if it doesn't fit into `u32`
+    pub fn payments(&self) -> Option<u32> {
+        match self.until {
+            RecurringLimit::Payments(payments) => Some(payments),
+            RecurringLimit::EndDate(end) if end >= self.start && self.interval > 0 => {
+                u32::try_from(((end - self.start) / self.interval).saturating_add(1)).ok()
+            }
+            RecurringLimit::EndDate(_) => Some(0),
+        }
+    }
+
+    // the number of payments that are due by the timestamp
+    pub fn due_payments(&self, timestamp: u64) -> u32 {
+        if timestamp < self.start || self.interval == 0 {
+            return 0;
+        }
+        let due = ((timestamp - self.start) / self.interval).saturating_add(1);
+        // the schedule is validated on submission, so the number of payments fits into `u32`
+        due.min(self.payments().unwrap_or_default() as u64) as u32
+    }
+}
+
+/// The maximum number of milestones of the grant.
+pub const MAX_MILESTONES: usize = 16;
+
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct Milestone {
+    /// the number of fungible tokens released on the milestone completion
+    pub amount: u128,
+    /// the deliverable of the milestone, at most [`MAX_TITLE_LENGTH`] bytes
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode, TypeInfo)]
+pub struct VestingSchedule {
+    /// the timestamp when the vesting starts
+    pub start: u64,
+    /// the time after `start` until which nothing can be claimed
+    pub cliff: u64,
+    /// the time after `start` when the whole amount is vested
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    pub fn vested(&self, amount: u128, timestamp: u64) -> u128 {
+        if timestamp < self.start.saturating_add(self.cliff) {
+            return 0;
+        }
+        let elapsed = timestamp - self.start;
+        if elapsed >= self.duration {
+            amount
+        } else {
+            amount.saturating_mul(elapsed as u128) / self.duration as u128
+        }
+    }
+}
+
+/// The grant created by the passed grant proposal. Its ID is the proposal ID.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct Grant {
+    pub recipient: ActorId,
+    /// the total amount of the grant (the released amount if the grant is cancelled)
+    pub amount: u128,
+    pub claimed: u128,
+    pub schedule: GrantSchedule,
+    pub cancelled: bool,
+    /// the number of milestones released by a vote or by admin signers
+    pub released_milestones: u32,
+}
+
+impl Grant {
+    // the amount released to the recipient by the timestamp
+    pub fn vested(&self, timestamp: u64) -> u128 {
+        if self.cancelled {
+            return self.amount;
+        }
+        match &self.schedule {
+            GrantSchedule::Vesting(schedule) => schedule.vested(self.amount, timestamp),
+            GrantSchedule::Milestones(milestones) => milestones
+                .iter()
+                .take(self.released_milestones as usize)
+                .map(|milestone| milestone.amount)
+                .fold(0, u128::saturating_add),
+            GrantSchedule::Recurring(schedule) => schedule
+                .payment
+                .saturating_mul(schedule.due_payments(timestamp) as u128)
+                .min(self.amount),
+        }
+    }
+
+    // the number of recurring payments that have been paid out
+    pub fn paid_periods(&self) -> u32 {
+        match &self.schedule {
+            GrantSchedule::Recurring(schedule) if schedule.payment > 0 => {
+                (self.claimed / schedule.payment) as u32
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// An action that can be executed only by a passed governance proposal.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub enum GovernanceAction {
+    GrantRole {
+        account: ActorId,
+        role: Role,
+    },
+    RevokeRole {
+        account: ActorId,
+        role: Role,
+    },
+    SetTreasurerSpendingLimit(u128),
+    UpdateWhitelist(WhitelistUpdate),
+    SetWhitelistMode(WhitelistMode),
+    SetMembershipGate(Option<MembershipGate>),
+    /// Cancels the part of the grant that hasn't been released yet.
+    CancelGrant(u128),
+    /// Releases the next milestone of the grant.
+    ReleaseMilestone {
+        grant_id: u128,
+        /// the index of the released milestone
+        milestone: u32,
+    },
+    /// Sets the budget that the treasurer can spend per epoch, the zero amount removes it.
+    SetBudget {
+        account: ActorId,
+        token: ActorId,
+        amount: u128,
+        periods_per_epoch: u64,
+    },
+    /// Sets who can receive the transferred shares.
+    SetShareTransferPolicy(ShareTransferPolicy),
+    /// Sets the rules of optimistic proposals, `None` disables them.
+    /// The objection threshold applies to the pending optimistic proposals as well,
+    /// and they are decided by the usual rule once optimistic proposals are disabled.
+    SetOptimisticConfig(Option<OptimisticConfig>),
+    /// Sets the parameters of conviction voting, `None` disables it.
+    SetConvictionConfig(Option<ConvictionConfig>),
+    /// Transfers the shares of the member if the share transfers require a vote.
+    TransferShares {
+        from: ActorId,
+        to: ActorId,
+        amount: u128,
+    },
+    /// Pays the fungible tokens out to the applicant,
+    /// the proposal fails if they exceed the funds that aren't reserved for grants.
+    Fund {
+        applicant: ActorId,
+        amount: u128,
+    },
+}
+
+/// The rules of optimistic proposals: small funding and grant proposals
+/// that pass at the end of the voting period unless the NO votes exceed the objection threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct OptimisticConfig {
+    /// the maximum number of fungible tokens the proposal can request
+    pub amount_cap: u128,
+    /// the maximum amount of native value the proposal can request
+    pub value_amount_cap: u128,
+    /// the percentage of all shares voting NO that rejects the proposal when exceeded
+    pub objection_threshold: u128,
+    /// the percentage of all shares voting either way required for the proposal to pass,
+    /// 0 is the lazy consensus where the proposal passes without any votes
+    pub quorum: u128,
+}
+
+/// Defines how members can transfer their shares.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ShareTransferPolicy {
+    /// Shares can't be transferred.
+    #[default]
+    Disabled,
+    /// Shares can be transferred only to DAO members.
+    MembersOnly,
+    /// Shares can be transferred to DAO members and whitelisted accounts.
+    Whitelisted,
+    /// Every transfer is a governance proposal.
+    RequiresVote,
+}
+
+/// The number of tokens that the treasurer can spend per epoch without proposals.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct Budget {
+    /// the fungible token program
+    pub token: ActorId,
+    /// the number of tokens that can be spent per epoch
+    pub amount: u128,
+    /// the epoch length in `period_duration`s
+    pub periods_per_epoch: u64,
+    /// the epoch of the last payout
+    pub epoch: u64,
+    /// the number of tokens spent during `epoch`
+    pub spent: u128,
+}
+
+/// The fixed-point base of the conviction parameters.
+pub const CONVICTION_BASE: u128 = 1_000_000;
+
+/// The parameters of conviction voting, all of them are fractions of [`CONVICTION_BASE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ConvictionConfig {
+    /// the part of the conviction that remains after each `period_duration`
+    pub decay: u128,
+    /// the part of the DAO balance that a request can never reach
+    pub max_ratio: u128,
+    /// the weight of the threshold relative to the total shares, it must not be zero
+    pub weight: u128,
+}
+
+impl ConvictionConfig {
+    // the decay to the power of the number of periods
+    fn decay_over(&self, periods: u64) -> u128 {
+        let (mut result, mut base, mut periods) = (CONVICTION_BASE, self.decay, periods);
+        while periods > 0 && result > 0 {
+            if periods % 2 == 1 {
+                result = result * base / CONVICTION_BASE;
+            }
+            base = base * base / CONVICTION_BASE;
+            periods /= 2;
+        }
+        result
+    }
+
+    /// The conviction after `periods` of staking `staked` shares,
+    /// it approaches `staked / (1 - decay)` over time.
+    pub fn accumulate(&self, conviction: u128, staked: u128, periods: u64) -> u128 {
+        let decay = self.decay_over(periods);
+        let accumulated =
+            staked.saturating_mul(CONVICTION_BASE - decay) / (CONVICTION_BASE - self.decay).max(1);
+        (conviction.saturating_mul(decay) / CONVICTION_BASE).saturating_add(accumulated)
+    }
+
+    /// The conviction that the request of `amount` needs to be executed:
+    /// `weight * total_shares / (max_ratio - amount / balance)^2`.
+    /// The request of `max_ratio` of the balance or more can't be executed.
+    pub fn threshold(&self, amount: u128, balance: u128, total_shares: u128) -> Option<u128> {
+        if balance == 0 {
+            return None;
+        }
+        let ratio = amount.saturating_mul(CONVICTION_BASE) / balance;
+        if ratio >= self.max_ratio {
+            return None;
+        }
+        let distance = self.max_ratio - ratio;
+        Some(
+            self.weight
+                .saturating_mul(total_shares)
+                .saturating_mul(CONVICTION_BASE)
+                / (distance * distance),
+        )
+    }
+}
+
+/// The comment on the proposal, its index is its position among the comments of the proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ProposalComment {
+    /// the member or the delegate address that posted the comment
+    pub author: ActorId,
+    /// the hash of the off-chain comment text
+    pub content_hash: [u8; 32],
+    pub posted_at: u64,
+}
+
+/// The funding request that is executed once the conviction of the staked shares is enough.
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo)]
+pub struct ConvictionRequest {
+    pub proposer: ActorId,
+    pub applicant: ActorId,
+    pub amount: u128,
+    /// the request title and the reference to its off-chain content
+    pub metadata: ProposalMetadata,
+    /// the shares staked by the members
+    pub stakes: Vec<(ActorId, u128)>,
+    pub staked: u128,
+    /// the conviction accumulated by the end of `updated_at` period
+    pub conviction: u128,
+    pub updated_at: u64,
+    pub executed: bool,
+}
+
+/// The rule accepting the applicants that aren't in the whitelist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum MembershipGate {
+    /// The applicant must own a token of the NFT program.
+    Nft(ActorId),
+    /// The applicant must hold at least `min_balance` tokens of the fungible token program.
+    Token { program: ActorId, min_balance: u128 },
+}
+
+/// Defines who can change the whitelist.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum WhitelistMode {
+    /// Whitelisters and admin signers.
+    #[default]
+    Admin,
+    /// Only passed governance proposals.
+    Governance,
+}
+
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo)]
+pub struct WhitelistUpdate {
+    /// accounts added to the whitelist
+    pub add: Vec<ActorId>,
+    /// the timestamp after which the added accounts are no longer whitelisted
+    pub expires_at: Option<u64>,
+    /// accounts removed from the whitelist
+    pub remove: Vec<ActorId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
+pub enum Role {
+    /// Changes the whitelist without the confirmations of admin signers.
+    Whitelister,
+    /// Pays out tokens from the DAO balance within the `treasurer_spending_limit` per period
+    /// and the budgets set by governance proposals.
+    Treasurer,
+    /// Processes proposals without being a DAO member.
+    ProposalProcessor,
+    /// Pauses the DAO in an emergency.
+    Guardian,
 }
 
 #[derive(Debug, Clone, Encode, Decode, TypeInfo, Default)]
 pub struct Member {
     pub delegate_key: ActorId,
     pub shares: u128,
-    pub highest_index_yes_vote: u128,
+    /// the proposals the member voted YES on with the number of shares voted,
+    /// the shares are locked until the proposal is processed or aborted
+    pub yes_votes: Vec<(u128, u128)>,
+    /// the proposals the member voted on in any way with the number of shares voted,
+    /// the shares can't be transferred until the proposal is processed or aborted
+    pub votes: Vec<(u128, u128)>,
+    /// the membership token ID if the DAO issues membership NFTs,
+    /// the token is owned by the member
+    pub token_id: Option<NftTokenId>,
+    /// the shares staked on the conviction requests
+    pub staked: u128,
+}
+
+/// The token ID of the non-fungible token program (`U256`).
+pub type NftTokenId = [u64; 4];
+
+/// An operation that requires confirmations of `admin_threshold` admin signers.
+#[derive(Debug, Decode, Encode, TypeInfo, Clone)]
+pub enum AdminOperation {
+    AddToWhiteList(ActorId),
+    RemoveFromWhitelist(ActorId),
+    UpdateWhitelist(WhitelistUpdate),
+    SetWhitelistMode(WhitelistMode),
+    SetMembershipGate(Option<MembershipGate>),
+    ProposeAdmin(ActorId),
+    GrantRole {
+        account: ActorId,
+        role: Role,
+    },
+    RevokeRole {
+        account: ActorId,
+        role: Role,
+    },
+    SetAdminSigners {
+        signers: Vec<ActorId>,
+        threshold: u32,
+    },
+    Pause,
+    Unpause,
+    ReleaseMilestone {
+        grant_id: u128,
+        milestone: u32,
+    },
+}
+
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct PendingAdminOperation {
+    pub operation: AdminOperation,
+    pub proposer: ActorId,
+    pub confirmations: Vec<ActorId>,
+    pub expires_at: u64,
 }
 
 #[derive(Debug, Decode, Encode, TypeInfo, Clone)]
@@ -113,21 +828,88 @@ pub enum DaoAction {
     /// Adds members to whitelist.
     ///
     /// Requirements:
-    /// * Only admin can add actors to whitelist;
+    /// * Only whitelisters or admin signers can add actors to whitelist;
+    /// * The whitelist must not be governed by proposals;
     /// * Member ID cant be zero;
     /// * Member can not be added to whitelist more than once;
     ///
     /// On success replies with [`DaoEvent::MemberAddedToWhitelist`]
+    /// if the sender is a whitelister or the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
     AddToWhiteList(
         /// valid actor ID
         ActorId,
     ),
 
+    /// Removes members from whitelist.
+    ///
+    /// Requirements:
+    /// * Only whitelisters or admin signers can remove actors from whitelist;
+    /// * The whitelist must not be governed by proposals;
+    /// * Member must be in the whitelist.
+    ///
+    /// On success replies with [`DaoEvent::MemberRemovedFromWhitelist`]
+    /// if the sender is a whitelister or the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    RemoveFromWhitelist(
+        /// the whitelisted actor ID
+        ActorId,
+    ),
+
+    /// Adds and removes several members of whitelist at once.
+    /// The added members stay in the whitelist until `expires_at` if it's set.
+    /// Members that are already in the whitelist get the new expiry.
+    ///
+    /// Requirements:
+    /// * Only whitelisters or admin signers can update whitelist;
+    /// * The whitelist must not be governed by proposals;
+    /// * The update must not be empty;
+    /// * The added member IDs cant be zero.
+    ///
+    /// On success replies with [`DaoEvent::WhitelistUpdated`]
+    /// if the sender is a whitelister or the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    UpdateWhitelist(WhitelistUpdate),
+
+    /// Sets who can change the whitelist.
+    /// Once the whitelist is governed by proposals,
+    /// only a governance proposal can change the mode back.
+    ///
+    /// Requirements:
+    /// * Only admin signers can set the whitelist mode;
+    /// * The whitelist must not be governed by proposals;
+    /// * The mode must differ from the current one.
+    ///
+    /// On success replies with [`DaoEvent::WhitelistModeUpdated`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    SetWhitelistMode(WhitelistMode),
+
+    /// Sets the rule accepting the applicants that aren't in the whitelist.
+    /// `None` removes the rule.
+    ///
+    /// Requirements:
+    /// * Only whitelisters or admin signers can set the rule;
+    /// * The whitelist must not be governed by proposals.
+    ///
+    /// On success replies with [`DaoEvent::MembershipGateUpdated`]
+    /// if the sender is a whitelister or the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    SetMembershipGate(Option<MembershipGate>),
+
     /// The proposal of joining the DAO.
+    /// The tribute can be paid in native value attached to the message instead of tokens.
+    /// The native value is held by the DAO program and returned to the proposer
+    /// if the proposal is rejected or aborted.
     ///
     /// Requirements:
     /// * The proposal can be submitted only by the existing members or their delegate addresses;
-    /// * The applicant account must be either a DAO member or is in the whitelist.
+    /// * The applicant account must be either a DAO member, in the whitelist
+    ///   or accepted by the membership gate that is checked in the gate program;
+    /// * The tribute can't be paid both in tokens and in native value;
+    /// * The tribute for the applicant checked by the membership gate can't be paid in native value.
+    ///
+    /// If the membership gate rejects the applicant, replies with [`DaoEvent::TransactionFailed`].
     ///
     /// On success replies with [`DaoEvent::SubmitMembershipProposal`]
     SubmitMembershipProposal {
@@ -139,16 +921,20 @@ pub enum DaoAction {
         shares_requested: u128,
         /// a certain threshold of YES votes in order for the proposal to pass
         quorum: u128,
-        /// the proposal description
-        details: String,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+        /// the token that the applicant owns in the NFT program of the membership gate
+        gate_token_id: Option<NftTokenId>,
     },
 
     /// The proposal of funding.
+    /// The receiver can be funded with tokens, native value or both.
     ///
     /// Requirements:
     /// * The proposal can be submitted only by the existing members or their delegate addresses;
     /// * The receiver ID can't be the zero;
     /// * The DAO must have enough funds to finance the proposal;
+    /// * The optimistic proposal must not request more than the caps of [`OptimisticConfig`].
     ///
     /// On success replies with [`DaoEvent::SubmitFundingProposal`]
     SubmitFundingProposal {
@@ -156,23 +942,170 @@ pub enum DaoAction {
         applicant: ActorId,
         /// the number of fungible tokens that will be sent to the receiver
         amount: u128,
+        /// the amount of native value that will be sent to the receiver
+        value_amount: u128,
+        /// a certain threshold of YES votes in order for the proposal to pass
+        /// (the optimistic proposal uses the quorum of [`OptimisticConfig`])
+        quorum: u128,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+        /// the proposal passes unless the NO votes exceed the objection threshold
+        optimistic: bool,
+    },
+
+    /// The proposal of a trade: the applicant offers the tribute in one token
+    /// and requests the payment in another token and optionally shares.
+    /// The tribute is held by the DAO until the proposal is processed or aborted
+    /// and returned to the applicant if the proposal is rejected.
+    /// The tokens other than the approved token are not redeemable by ragequit.
+    ///
+    /// Requirements:
+    /// * The proposal can be submitted only by the existing members or their delegate addresses;
+    /// * The applicant must be either a DAO member or in the whitelist if it requests shares;
+    /// * The applicant and token IDs can't be the zero;
+    /// * The proposal must offer or request something.
+    ///
+    /// On success replies with [`DaoEvent::SubmitTradeProposal`]
+    SubmitTradeProposal {
+        /// an actor who offers the tribute
+        applicant: ActorId,
+        /// the fungible token program of the tribute
+        tribute_token: ActorId,
+        /// the number of tokens the applicant offers
+        tribute_offered: u128,
+        /// the fungible token program of the payment
+        payment_token: ActorId,
+        /// the number of tokens the applicant requests
+        payment_requested: u128,
+        /// the amount of shares the applicant requests
+        shares_requested: u128,
         /// a certain threshold of YES votes in order for the proposal to pass
         quorum: u128,
-        /// the proposal description
-        details: String,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+    },
+
+    /// The proposal of funding released by the vesting schedule, by milestones or in recurring payments.
+    /// If the proposal passes, the recipient claims the released tokens with [`DaoAction::ClaimGrant`].
+    /// The unclaimed amount is not redeemable by ragequit.
+    ///
+    /// Requirements:
+    /// * The proposal can be submitted only by the existing members or their delegate addresses;
+    /// * The receiver ID can't be the zero;
+    /// * The amount must not be zero;
+    /// * The vesting duration must not be zero and the cliff must not exceed it;
+    /// * There can't be more than [`MAX_MILESTONES`] milestones;
+    /// * The milestone amounts must not be zero and must add up to the amount;
+    /// * The milestone descriptions can't be longer than [`MAX_TITLE_LENGTH`] bytes;
+    /// * The recurring payment and interval must not be zero
+    ///   and all the payments must add up to the amount;
+    /// * The optimistic proposal must not request more than the amount cap of [`OptimisticConfig`].
+    ///
+    /// On success replies with [`DaoEvent::SubmitGrantProposal`]
+    SubmitGrantProposal {
+        /// an actor that will be funded
+        applicant: ActorId,
+        /// the total number of fungible tokens that will be released to the receiver
+        amount: u128,
+        /// the release schedule
+        schedule: GrantSchedule,
+        /// a certain threshold of YES votes in order for the proposal to pass
+        /// (the optimistic proposal uses the quorum of [`OptimisticConfig`])
+        quorum: u128,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+        /// the proposal passes unless the NO votes exceed the objection threshold
+        optimistic: bool,
+    },
+
+    /// Transfers the released tokens of the grant to its recipient.
+    /// Due recurring payments can be triggered by anyone.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the grant recipient unless the grant is recurring;
+    /// * The DAO must not be paused;
+    /// * The grant must have tokens to claim.
+    ///
+    /// On success replies with [`DaoEvent::GrantClaimed`]
+    ClaimGrant(
+        /// the grant ID
+        u128,
+    ),
+
+    /// The proposal of executing the governance action.
+    ///
+    /// Requirements:
+    /// * The proposal can be submitted only by the existing members or their delegate addresses;
+    /// * The actor IDs in the action can't be zero.
+    ///
+    /// On success replies with [`DaoEvent::SubmitGovernanceProposal`]
+    SubmitGovernanceProposal {
+        /// the action executed if the proposal passes
+        action: GovernanceAction,
+        /// a certain threshold of YES votes in order for the proposal to pass
+        quorum: u128,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+    },
+
+    /// The signaling proposal that has no on-chain effect.
+    /// The votes on it are recorded as usual, but they don't lock the shares of the members.
+    ///
+    /// Requirements:
+    /// * The proposal can be submitted only by the existing members or their delegate addresses.
+    ///
+    /// On success replies with [`DaoEvent::SubmitSignalingProposal`]
+    SubmitSignalingProposal {
+        /// a certain threshold of YES votes in order for the proposal to pass
+        quorum: u128,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+    },
+
+    /// The proposal choosing one of several options by ranked ballots.
+    /// The winner is found by the instant runoff and its action is executed.
+    ///
+    /// Requirements:
+    /// * The proposal can be submitted only by the existing members or their delegate addresses;
+    /// * There must be from 2 to `MAX_OPTIONS` options;
+    /// * The option descriptions can't be longer than [`MAX_TITLE_LENGTH`] bytes;
+    /// * The actor IDs in the actions can't be zero.
+    ///
+    /// On success replies with [`DaoEvent::SubmitRankedChoiceProposal`]
+    SubmitRankedChoiceProposal {
+        /// the options to choose from
+        options: Vec<ProposalOption>,
+        /// a certain threshold of the ballots in order for the proposal to pass
+        quorum: u128,
+        /// the proposal title and the reference to its off-chain content
+        metadata: ProposalMetadata,
     },
 
     /// The proposal processing after the proposal completes during the grace period.
     /// If the membership proposal is accepted, the tribute tokens are deposited into the contract
     /// and new shares are minted and issued to the applicant.
+    /// If the DAO issues membership NFTs, the account receiving its first shares
+    /// through the proposal also receives the membership token, and the member transferring away
+    /// all the shares through the proposal loses it. The share transfer is skipped
+    /// if that member hasn't approved the DAO for the token.
     /// If the membership proposal is rejected, the tribute tokens are returned to the applicant.
     /// If the funding proposal is accepted, the indicated amount of tokens is transfered to the applicant;
+    /// The native value tribute and funding are handled the same way,
+    /// but the funding proposal fails if the DAO doesn't hold enough native value.
     /// If the funging proposal is rejected, the indicated amount of tokens remains in the contract.
+    /// If the governance proposal is accepted, its action is executed.
+    /// If the grant proposal is accepted, the grant is created and its amount is reserved;
+    /// the grant proposal fails if the DAO doesn't have enough unreserved funds.
+    /// If the trade proposal is accepted, the payment is transferred to the applicant,
+    /// the tribute is deposited and the shares are issued;
+    /// the trade proposal fails if the DAO doesn't have enough tokens for the payment.
+    /// If the trade proposal is rejected, the tribute is returned to the applicant.
     ///
     /// Requirements:
-    /// * The previous proposal must be processed;
+    /// * `msg::source()` must be a DAO member, a delegate or a proposal processor;
+    /// * The previous proposal in the same lane must be processed, aborted or cancelled;
     /// * The proposal must exist and be ready for processing;
-    /// * The proposal must not be aborted or already be processed.
+    /// * The proposal must not be aborted, cancelled or already be processed.
     ///
     /// On success replies with [`DaoEvent::ProcessProposal`]
     ProcessProposal(
@@ -185,8 +1118,9 @@ pub enum DaoAction {
     /// Requirements:
     /// * The proposal can be submitted only by the existing members or their delegate addresses;
     /// * The member can vote on the proposal only once;
-    /// * Proposal must exist, the voting period must has started and not expired;
-    /// * Proposal must not be aborted.
+    /// * Proposal must exist, the voting period must has started and not expired or been closed;
+    /// * Proposal must not be aborted;
+    /// * The text reason can't be longer than [`MAX_REASON_LENGTH`].
     ///
     /// On success replies with [`DaoEvent::SubmitVote`]
     SubmitVote {
@@ -194,12 +1128,70 @@ pub enum DaoAction {
         proposal_id: u128,
         /// the member  a member vote (YES or NO)
         vote: Vote,
+        /// the reason of the vote recorded with it
+        reason: Option<VoteReason>,
+    },
+
+    /// The member (or the delegate address of the member) submits the ranked ballot
+    /// on the ranked-choice proposal.
+    /// The shares of the ballot are locked as the shares voted YES until the proposal is processed.
+    ///
+    /// Requirements:
+    /// * The ballot can be submitted only by the existing members or their delegate addresses;
+    /// * The member can vote on the proposal only once;
+    /// * Proposal must be ranked-choice, the voting period must has started and not expired;
+    /// * The ranking must be non-empty and list the existing options at most once.
+    ///
+    /// On success replies with [`DaoEvent::SubmitRankedVote`]
+    SubmitRankedVote {
+        /// the proposal ID
+        proposal_id: u128,
+        /// the option indices from the most preferred
+        ranking: Vec<u32>,
+    },
+
+    /// Closes the voting early if the outcome of the proposal is already decided:
+    /// the YES votes are more than half of all shares and meet the quorum,
+    /// or the NO votes can no longer be overcome.
+    /// The passing proposal must also keep within the dilution bound.
+    /// The grace period starts right away.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a DAO member, a delegate or a proposal processor;
+    /// * The voting period must have started and not expired or been closed;
+    /// * The abort window of the proposal must be over;
+    /// * The proposal must not be optimistic, processed, aborted or cancelled.
+    ///
+    /// On success replies with [`DaoEvent::VotingClosed`]
+    CloseVoting(
+        /// the proposal ID
+        u128,
+    ),
+
+    /// Anchors the comment on the proposal, e.g. the reason of the vote or an amendment.
+    /// The text of the comment is stored off-chain.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a DAO member or a delegate;
+    /// * The proposal must not be processed, aborted or cancelled and its voting must not be over;
+    /// * The member can comment once per `period_duration`.
+    ///
+    /// On success replies with [`DaoEvent::Commented`]
+    Comment {
+        /// the proposal ID
+        proposal_id: u128,
+        /// the hash of the off-chain comment text
+        content_hash: [u8; 32],
     },
 
     /// Withdraws the capital of the member.
+    /// The member receives his share of both the token balance and the native value balance.
+    /// The membership token is taken back and burned if the member withdraws all the shares.
     ///
     /// Requirements:
     /// * `msg::source()` must be DAO member;
+    /// * The member withdrawing all the shares must approve the DAO for the membership token,
+    ///   otherwise the transaction fails and can be continued after the approval;
     /// * The member must have sufficient amount;
     /// * The latest proposal the member voted YES must be processed;
     /// * Admin can ragequit only after transferring his role to another actor.
@@ -210,15 +1202,15 @@ pub enum DaoAction {
         u128,
     ),
 
-    /// Aborts the membership proposal.
+    /// Aborts the membership or the trade proposal.
     /// It can be used in case when applicant is disagree with the requested shares
     /// or the details the proposer indicated by the proposer.
     ///
     /// Requirements:
     /// * `msg::source()` must be the applicant;
-    /// * The proposal must be membership proposal;
+    /// * The proposal must be membership or trade proposal;
     /// * The proposal can be aborted during only the abort window
-    /// * The proposal has not be already aborted.
+    /// * The proposal has not be already processed, aborted or cancelled.
     ///
     /// On success replies with [`DaoEvent::Abort`]
     Abort(
@@ -226,6 +1218,43 @@ pub enum DaoAction {
         u128,
     ),
 
+    /// Cancels the proposal, e.g. if the proposer made a mistake in its details.
+    /// The escrowed token tribute is returned to the applicant
+    /// and the native value tribute is returned to the proposer.
+    /// The cancelled proposal is skipped in its lane.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the proposer;
+    /// * The voting period must not have started or no one must have voted yet;
+    /// * The proposal must not be processed, aborted or already be cancelled.
+    ///
+    /// On success replies with [`DaoEvent::CancelProposal`]
+    CancelProposal(
+        /// the proposal ID
+        u128,
+    ),
+
+    /// Transfers the shares of the member to another account.
+    /// The recipient becomes a DAO member if it isn't one.
+    /// If the DAO issues membership NFTs, the recipient without the membership token receives one
+    /// and the token of the member transferring all the shares is taken back and burned.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be DAO member with sufficient shares;
+    /// * The member transferring all the shares must approve the DAO for the membership token,
+    ///   otherwise the transaction fails and can be continued after the approval;
+    /// * The recipient must be allowed by `share_transfer_policy`;
+    /// * The latest proposal the member voted YES must be processed;
+    /// * The recipient can't be the zero address or the member itself.
+    ///
+    /// On success replies with [`DaoEvent::SharesTransferred`]
+    TransferShares {
+        /// the actor receiving the shares
+        to: ActorId,
+        /// the amount of shares
+        amount: u128,
+    },
+
     /// Sets the delegate key that is responsible for submitting proposals and voting;
     /// The deleagate key defaults to member address unless updated.
     ///
@@ -240,17 +1269,247 @@ pub enum DaoAction {
         ActorId,
     ),
 
-    /// Assigns the admin position to new actor.
+    /// Proposes the new actor for the admin position.
+    /// The new admin replaces the signer who proposed it after accepting the position
+    /// with [`DaoAction::AcceptAdmin`], the other signers and the threshold are kept.
     ///
     /// Requirements:
-    /// * Only admin can assign new admin.
+    /// * Only admin signers can propose new admin;
+    /// * The new admin can't be the zero address or an admin signer.
     ///
-    /// On success replies with [`DaoEvent::AdminUpdated`]
-    SetAdmin(
+    /// On success replies with [`DaoEvent::AdminTransferProposed`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    ProposeAdmin(
         /// New admin account
         ActorId,
     ),
 
+    /// Accepts the admin position proposed by [`DaoAction::ProposeAdmin`].
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the proposed admin.
+    ///
+    /// On success replies with [`DaoEvent::AdminUpdated`]
+    AcceptAdmin,
+
+    /// Cancels the admin transfer proposed by [`DaoAction::ProposeAdmin`].
+    ///
+    /// Requirements:
+    /// * Only admin signers can cancel the admin transfer;
+    /// * The admin transfer must be proposed.
+    ///
+    /// On success replies with [`DaoEvent::AdminTransferCancelled`]
+    CancelAdminTransfer,
+
+    /// Replaces the admin signers and the number of confirmations
+    /// required to execute an admin operation.
+    /// All pending admin operations and the pending admin transfer are discarded.
+    ///
+    /// Requirements:
+    /// * Only admin signers can set new signers;
+    /// * Signers must not be empty, contain the zero address or duplicates;
+    /// * The threshold must be between 1 and the number of signers.
+    ///
+    /// On success replies with [`DaoEvent::AdminSignersUpdated`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    SetAdminSigners {
+        /// new admin signers
+        signers: Vec<ActorId>,
+        /// the number of confirmations required to execute an admin operation
+        threshold: u32,
+    },
+
+    /// Pauses the submission of proposals, voting, processing of proposals,
+    /// treasurer payouts, budget spending, grant claims and conviction payouts.
+    /// Ragequit and abort remain available.
+    ///
+    /// Requirements:
+    /// * Only guardians or admin signers can pause the DAO;
+    /// * The DAO must not be paused.
+    ///
+    /// On success replies with [`DaoEvent::Paused`]
+    /// if the sender is a guardian or the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    Pause,
+
+    /// Unpauses the DAO.
+    ///
+    /// Requirements:
+    /// * Only admin signers can unpause the DAO;
+    /// * The DAO must be paused.
+    ///
+    /// On success replies with [`DaoEvent::Unpaused`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    Unpause,
+
+    /// Grants the role to the actor.
+    /// Roles can also be granted by a governance proposal.
+    ///
+    /// Requirements:
+    /// * Only admin signers can grant roles;
+    /// * The actor ID can't be zero.
+    ///
+    /// On success replies with [`DaoEvent::RoleGranted`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    GrantRole {
+        /// the actor receiving the role
+        account: ActorId,
+        /// the granted role
+        role: Role,
+    },
+
+    /// Revokes the role from the actor.
+    /// Roles can also be revoked by a governance proposal.
+    ///
+    /// Requirements:
+    /// * Only admin signers can revoke roles;
+    /// * The actor must have the role.
+    ///
+    /// On success replies with [`DaoEvent::RoleRevoked`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    RevokeRole {
+        /// the actor losing the role
+        account: ActorId,
+        /// the revoked role
+        role: Role,
+    },
+
+    /// Transfers tokens from the DAO balance to the recipient without a proposal.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a treasurer;
+    /// * The DAO must not be paused;
+    /// * The amount must not exceed the DAO balance and what is left of the `treasurer_spending_limit`
+    ///   for the current `period_duration`, the limit is shared by all treasurers;
+    /// * The recipient can't be the zero address.
+    ///
+    /// On success replies with [`DaoEvent::TreasurerPayout`]
+    TreasurerPayout {
+        /// the actor receiving the tokens
+        recipient: ActorId,
+        /// the number of fungible tokens
+        amount: u128,
+    },
+
+    /// Transfers tokens to the recipient within the budget set by a governance proposal.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a treasurer with the budget for the token;
+    /// * The DAO must not be paused;
+    /// * The amount must not exceed the budget left for the current epoch;
+    /// * The DAO must have enough unreserved funds of the token, the escrowed tributes are excluded;
+    /// * The recipient can't be the zero address.
+    ///
+    /// On success replies with [`DaoEvent::BudgetSpent`]
+    SpendBudget {
+        /// the fungible token program
+        token: ActorId,
+        /// the actor receiving the tokens
+        recipient: ActorId,
+        /// the number of fungible tokens
+        amount: u128,
+    },
+
+    /// Releases the next milestone of the grant by the admin signers.
+    /// Milestones can also be released by a governance proposal.
+    ///
+    /// Requirements:
+    /// * Only admin signers can release milestones;
+    /// * The grant must be a milestone grant that hasn't been cancelled;
+    /// * The milestone must be the next unreleased one.
+    ///
+    /// On success replies with [`DaoEvent::MilestoneReleased`]
+    /// if the operation is confirmed by `admin_threshold` signers,
+    /// otherwise with [`DaoEvent::AdminOperationProposed`].
+    ReleaseMilestone {
+        /// the grant ID
+        grant_id: u128,
+        /// the index of the released milestone
+        milestone: u32,
+    },
+
+    /// Confirms the pending admin operation.
+    /// The operation is executed as soon as it has `admin_threshold` confirmations.
+    ///
+    /// Requirements:
+    /// * Only admin signers can confirm operations;
+    /// * The operation must exist and must not be expired;
+    /// * The signer can confirm the operation only once.
+    ///
+    /// On success replies with the event of the executed operation
+    /// or with [`DaoEvent::AdminOperationConfirmed`].
+    ConfirmAdminOperation(
+        /// the admin operation ID
+        u64,
+    ),
+
+    /// Submits the funding request that is executed once the staked shares
+    /// accumulate enough conviction instead of voting in a fixed period.
+    ///
+    /// Requirements:
+    /// * The request can be submitted only by the existing members or their delegate addresses;
+    /// * Conviction voting must be enabled by a governance proposal;
+    /// * The applicant ID can't be the zero and the amount must not be zero;
+    /// * The metadata title and URI must not exceed their length limits.
+    ///
+    /// On success replies with [`DaoEvent::ConvictionRequestSubmitted`]
+    SubmitConvictionRequest {
+        /// an actor that will be funded
+        applicant: ActorId,
+        /// the number of fungible tokens that will be sent to the applicant
+        amount: u128,
+        /// the request title and the reference to its off-chain content
+        metadata: ProposalMetadata,
+    },
+
+    /// Stakes the shares of the member on the conviction request.
+    /// The staked shares can't be withdrawn by ragequit or transferred.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the delegate key of a DAO member;
+    /// * The member must have enough shares that aren't staked;
+    /// * The request must exist and must not be executed.
+    ///
+    /// On success replies with [`DaoEvent::ConvictionStaked`]
+    StakeConviction {
+        /// the request ID
+        request_id: u128,
+        /// the number of shares to stake
+        amount: u128,
+    },
+
+    /// Withdraws the shares staked on the conviction request.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the delegate key of a DAO member;
+    /// * The member must have staked at least `amount` shares on the request.
+    ///
+    /// On success replies with [`DaoEvent::ConvictionWithdrawn`]
+    WithdrawConviction {
+        /// the request ID
+        request_id: u128,
+        /// the number of shares to withdraw
+        amount: u128,
+    },
+
+    /// Pays out the conviction request and releases its stakes.
+    ///
+    /// Requirements:
+    /// * The DAO must not be paused;
+    /// * The request must exist and must not be executed;
+    /// * The conviction must reach the threshold that grows with the part of the DAO balance requested.
+    ///
+    /// On success replies with [`DaoEvent::ConvictionRequestExecuted`]
+    ExecuteConvictionRequest(
+        /// the request ID
+        u128,
+    ),
+
     /// Continues the transaction if it fails due to lack of gas
     /// or due to an error in the token contract.
     ///
@@ -267,22 +1526,77 @@ pub enum DaoAction {
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum DaoEvent {
     MemberAddedToWhitelist(ActorId),
+    MemberRemovedFromWhitelist(ActorId),
+    WhitelistUpdated {
+        added: Vec<ActorId>,
+        removed: Vec<ActorId>,
+    },
+    WhitelistModeUpdated(WhitelistMode),
+    MembershipGateUpdated(Option<MembershipGate>),
     SubmitMembershipProposal {
         proposer: ActorId,
         applicant: ActorId,
         proposal_id: u128,
         token_tribute: u128,
+        value_tribute: u128,
     },
     SubmitFundingProposal {
         proposer: ActorId,
         applicant: ActorId,
         proposal_id: u128,
         amount: u128,
+        value_amount: u128,
     },
     SubmitVote {
         account: ActorId,
         proposal_id: u128,
         vote: Vote,
+        reason: Option<VoteReason>,
+    },
+    SubmitRankedVote {
+        account: ActorId,
+        proposal_id: u128,
+        ranking: Vec<u32>,
+    },
+    VotingClosed {
+        proposal_id: u128,
+        closed_at: u64,
+    },
+    SubmitGovernanceProposal {
+        proposer: ActorId,
+        proposal_id: u128,
+    },
+    SubmitRankedChoiceProposal {
+        proposer: ActorId,
+        proposal_id: u128,
+    },
+    SubmitSignalingProposal {
+        proposer: ActorId,
+        proposal_id: u128,
+    },
+    SubmitGrantProposal {
+        proposer: ActorId,
+        applicant: ActorId,
+        proposal_id: u128,
+        amount: u128,
+    },
+    SubmitTradeProposal {
+        proposer: ActorId,
+        applicant: ActorId,
+        proposal_id: u128,
+        tribute_offered: u128,
+        payment_requested: u128,
+        shares_requested: u128,
+    },
+    GrantClaimed {
+        grant_id: u128,
+        recipient: ActorId,
+        amount: u128,
+    },
+    MilestoneReleased {
+        grant_id: u128,
+        milestone: u32,
+        amount: u128,
     },
     ProcessProposal {
         proposal_id: u128,
@@ -291,9 +1605,82 @@ pub enum DaoEvent {
     RageQuit {
         member: ActorId,
         amount: u128,
+        value: u128,
     },
     Abort(u128),
+    CancelProposal(u128),
+    Commented {
+        proposal_id: u128,
+        comment_index: u32,
+        author: ActorId,
+        content_hash: [u8; 32],
+    },
+    AdminTransferProposed(ActorId),
+    AdminTransferCancelled(ActorId),
     AdminUpdated(ActorId),
+    AdminSignersUpdated {
+        signers: Vec<ActorId>,
+        threshold: u32,
+    },
+    AdminOperationProposed {
+        operation_id: u64,
+        proposer: ActorId,
+    },
+    AdminOperationConfirmed {
+        operation_id: u64,
+        signer: ActorId,
+        confirmations: u32,
+    },
+    Paused,
+    Unpaused,
+    RoleGranted {
+        account: ActorId,
+        role: Role,
+    },
+    RoleRevoked {
+        account: ActorId,
+        role: Role,
+    },
+    TreasurerPayout {
+        treasurer: ActorId,
+        recipient: ActorId,
+        amount: u128,
+    },
+    BudgetSpent {
+        treasurer: ActorId,
+        token: ActorId,
+        recipient: ActorId,
+        amount: u128,
+        epoch: u64,
+        /// the number of tokens spent during the epoch including this payout
+        spent: u128,
+    },
+    SharesTransferred {
+        from: ActorId,
+        to: ActorId,
+        amount: u128,
+    },
+    ConvictionRequestSubmitted {
+        proposer: ActorId,
+        applicant: ActorId,
+        request_id: u128,
+        amount: u128,
+    },
+    ConvictionStaked {
+        member: ActorId,
+        request_id: u128,
+        amount: u128,
+    },
+    ConvictionWithdrawn {
+        member: ActorId,
+        request_id: u128,
+        amount: u128,
+    },
+    ConvictionRequestExecuted {
+        request_id: u128,
+        applicant: ActorId,
+        amount: u128,
+    },
     DelegateKeyUpdated {
         member: ActorId,
         delegate: ActorId,
@@ -310,6 +1697,13 @@ pub struct InitDao {
     pub grace_period_length: u64,
     pub dilution_bound: u8,
     pub abort_window: u64,
+    pub admin_operation_lifetime: u64,
+    /// the NFT program issuing membership tokens to the new members,
+    /// the DAO mints the tokens and transfers them to the members,
+    /// the members approve the DAO for their tokens, so it's able to burn them when they leave
+    pub membership_nft: Option<ActorId>,
+    /// the rules of optimistic proposals, they are disabled if it's `None`
+    pub optimistic: Option<OptimisticConfig>,
 }
 
 #[derive(Debug, Encode, Decode, Clone, TypeInfo)]
@@ -317,3 +1711,15 @@ pub enum Vote {
     Yes,
     No,
 }
+
+/// The maximum length of the text vote reason in bytes.
+pub const MAX_REASON_LENGTH: usize = 256;
+
+/// Why the member voted the way it did.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub enum VoteReason {
+    /// the short text of at most [`MAX_REASON_LENGTH`] bytes
+    Text(String),
+    /// the hash of the off-chain text
+    Hash([u8; 32]),
+}
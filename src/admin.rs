@@ -0,0 +1,257 @@
+use crate::contract::Dao;
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    pub fn propose_admin_operation(&mut self, operation: AdminOperation) {
+        self.assert_admin();
+        self.validate_admin_operation(&operation);
+
+        // expired operations can no longer be confirmed
+        let now = exec::block_timestamp();
+        self.admin_operations
+            .retain(|_, pending| pending.expires_at >= now);
+
+        let operation_id = self.admin_operation_id;
+        self.admin_operation_id = self.admin_operation_id.wrapping_add(1);
+
+        if self.admin_threshold <= 1 {
+            let event = self.execute_admin_operation(operation, &msg::source());
+            msg::reply(event, 0).expect("Error in a reply `DaoEvent`");
+            return;
+        }
+
+        self.admin_operations.insert(
+            operation_id,
+            PendingAdminOperation {
+                operation,
+                proposer: msg::source(),
+                confirmations: vec![msg::source()],
+                expires_at: now.saturating_add(self.admin_operation_lifetime),
+            },
+        );
+        msg::reply(
+            DaoEvent::AdminOperationProposed {
+                operation_id,
+                proposer: msg::source(),
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::AdminOperationProposed`");
+    }
+
+    pub fn confirm_admin_operation(&mut self, operation_id: u64) {
+        self.assert_admin();
+        let pending = self
+            .admin_operations
+            .get_mut(&operation_id)
+            .expect("Admin operation does not exist");
+
+        if exec::block_timestamp() > pending.expires_at {
+            panic!("Admin operation has expired");
+        }
+        if pending.confirmations.contains(&msg::source()) {
+            panic!("Signer has already confirmed this operation");
+        }
+        pending.confirmations.push(msg::source());
+        let confirmations = pending.confirmations.len() as u32;
+
+        if confirmations < self.admin_threshold {
+            msg::reply(
+                DaoEvent::AdminOperationConfirmed {
+                    operation_id,
+                    signer: msg::source(),
+                    confirmations,
+                },
+                0,
+            )
+            .expect("Error in a reply `DaoEvent::AdminOperationConfirmed`");
+            return;
+        }
+
+        let pending = self
+            .admin_operations
+            .remove(&operation_id)
+            .expect("Can't be None");
+        // the state might have changed since the operation was proposed
+        self.validate_admin_operation(&pending.operation);
+        let event = self.execute_admin_operation(pending.operation, &pending.proposer);
+        msg::reply(event, 0).expect("Error in a reply `DaoEvent`");
+    }
+
+    pub fn validate_admin_operation(&self, operation: &AdminOperation) {
+        match operation {
+            AdminOperation::AddToWhiteList(member) => {
+                self.assert_whitelist_managed_by_admin();
+                Self::assert_not_zero_address(member);
+                if self.is_whitelisted(member) {
+                    panic!("Member has already been added to the whitelist");
+                }
+            }
+            AdminOperation::RemoveFromWhitelist(member) => {
+                self.assert_whitelist_managed_by_admin();
+                if !self.whitelist.contains_key(member) {
+                    panic!("Member is not in the whitelist");
+                }
+            }
+            AdminOperation::UpdateWhitelist(update) => {
+                self.assert_whitelist_managed_by_admin();
+                Self::validate_whitelist_update(update);
+            }
+            AdminOperation::SetWhitelistMode(mode) => {
+                self.assert_whitelist_managed_by_admin();
+                if *mode == self.whitelist_mode {
+                    panic!("Whitelist mode is already set");
+                }
+            }
+            AdminOperation::SetMembershipGate(_) => self.assert_whitelist_managed_by_admin(),
+            AdminOperation::ProposeAdmin(new_admin) => {
+                Self::assert_not_zero_address(new_admin);
+                if self.admins.contains(new_admin) {
+                    panic!("Account is already an admin signer");
+                }
+            }
+            AdminOperation::GrantRole { account, .. } => Self::assert_not_zero_address(account),
+            AdminOperation::RevokeRole { account, role } => {
+                if !self.has_role(account, *role) {
+                    panic!("Account does not have the role");
+                }
+            }
+            AdminOperation::SetAdminSigners { signers, threshold } => {
+                if signers.is_empty() {
+                    panic!("Admin signers can't be empty");
+                }
+                if *threshold == 0 || *threshold as usize > signers.len() {
+                    panic!("Invalid admin threshold");
+                }
+                for (i, signer) in signers.iter().enumerate() {
+                    Self::assert_not_zero_address(signer);
+                    if signers[..i].contains(signer) {
+                        panic!("Duplicate admin signer");
+                    }
+                }
+            }
+            AdminOperation::Pause => {
+                if self.paused {
+                    panic!("DAO is already paused");
+                }
+            }
+            AdminOperation::Unpause => {
+                if !self.paused {
+                    panic!("DAO is not paused");
+                }
+            }
+            AdminOperation::ReleaseMilestone {
+                grant_id,
+                milestone,
+            } => self.validate_milestone_release(*grant_id, *milestone),
+        }
+    }
+
+    // `proposer` is the signer who proposed the operation
+    pub fn execute_admin_operation(
+        &mut self,
+        operation: AdminOperation,
+        proposer: &ActorId,
+    ) -> DaoEvent {
+        match operation {
+            AdminOperation::AddToWhiteList(member) => {
+                self.whitelist.insert(member, None);
+                DaoEvent::MemberAddedToWhitelist(member)
+            }
+            AdminOperation::RemoveFromWhitelist(member) => {
+                self.whitelist.remove(&member);
+                DaoEvent::MemberRemovedFromWhitelist(member)
+            }
+            AdminOperation::UpdateWhitelist(update) => {
+                self.apply_whitelist_update(&update);
+                DaoEvent::WhitelistUpdated {
+                    added: update.add,
+                    removed: update.remove,
+                }
+            }
+            AdminOperation::SetWhitelistMode(mode) => {
+                self.whitelist_mode = mode;
+                DaoEvent::WhitelistModeUpdated(mode)
+            }
+            AdminOperation::SetMembershipGate(gate) => {
+                self.membership_gate = gate;
+                DaoEvent::MembershipGateUpdated(gate)
+            }
+            AdminOperation::ProposeAdmin(new_admin) => {
+                // the new admin takes the place of the signer who proposed it
+                self.pending_admin = Some((new_admin, *proposer));
+                DaoEvent::AdminTransferProposed(new_admin)
+            }
+            AdminOperation::GrantRole { account, role } => {
+                self.grant_role(&account, role);
+                DaoEvent::RoleGranted { account, role }
+            }
+            AdminOperation::RevokeRole { account, role } => {
+                self.revoke_role(&account, role);
+                DaoEvent::RoleRevoked { account, role }
+            }
+            AdminOperation::SetAdminSigners { signers, threshold } => {
+                self.set_admin_signers(signers.clone(), threshold);
+                DaoEvent::AdminSignersUpdated { signers, threshold }
+            }
+            AdminOperation::Pause => {
+                self.paused = true;
+                DaoEvent::Paused
+            }
+            AdminOperation::Unpause => {
+                self.paused = false;
+                DaoEvent::Unpaused
+            }
+            AdminOperation::ReleaseMilestone {
+                grant_id,
+                milestone,
+            } => DaoEvent::MilestoneReleased {
+                grant_id,
+                milestone,
+                amount: self.release_milestone(grant_id, milestone),
+            },
+        }
+    }
+
+    pub fn accept_admin(&mut self) {
+        let new_admin = msg::source();
+        let replaced_admin = match self.pending_admin {
+            Some((account, replaced_admin)) if account == new_admin => replaced_admin,
+            _ => panic!("msg::source() must be the proposed admin"),
+        };
+        // the other signers and the threshold are kept
+        let signers = self
+            .admins
+            .iter()
+            .map(|signer| {
+                if *signer == replaced_admin {
+                    new_admin
+                } else {
+                    *signer
+                }
+            })
+            .collect();
+        self.set_admin_signers(signers, self.admin_threshold);
+        msg::reply(DaoEvent::AdminUpdated(new_admin), 0)
+            .expect("Error in a reply `DaoEvent::AdminUpdated`");
+    }
+
+    pub fn cancel_admin_transfer(&mut self) {
+        self.assert_admin();
+        let (new_admin, _) = self
+            .pending_admin
+            .take()
+            .expect("Admin transfer has not been proposed");
+        msg::reply(DaoEvent::AdminTransferCancelled(new_admin), 0)
+            .expect("Error in a reply `DaoEvent::AdminTransferCancelled`");
+    }
+
+    fn set_admin_signers(&mut self, signers: Vec<ActorId>, threshold: u32) {
+        self.admins = signers;
+        self.admin_threshold = threshold;
+        // confirmations of the previous signers are no longer valid
+        self.admin_operations.clear();
+        self.pending_admin = None;
+    }
+}
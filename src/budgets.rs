@@ -0,0 +1,109 @@
+use crate::{contract::Dao, ft_messages::transfer_tokens};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    pub async fn spend_budget(
+        &mut self,
+        transaction_id: Option<u64>,
+        token: &ActorId,
+        recipient: &ActorId,
+        amount: u128,
+    ) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
+        if !self.has_role(&msg::source(), Role::Treasurer) {
+            panic!("msg::source() must be DAO treasurer");
+        }
+        Self::assert_not_zero_address(recipient);
+        let budget = self
+            .budgets
+            .get(&(msg::source(), *token))
+            .expect("Treasurer has no budget for the token");
+        let epoch = self.budget_epoch(budget);
+        let spent = if budget.epoch == epoch {
+            budget.spent
+        } else {
+            0
+        }
+        .saturating_add(amount);
+        if spent > budget.amount {
+            panic!("Amount exceeds the budget left for the epoch");
+        }
+        // the tributes escrowed for pending proposals aren't on the DAO balance
+        if amount > self.token_balance(token) {
+            panic!("Insufficient DAO balance");
+        }
+
+        // the tokens are on the DAO balance
+        // we have to rerun that transaction to transfer tokens to recipient in case of error
+        if transfer_tokens(
+            current_transaction_id,
+            token,
+            &exec::program_id(),
+            recipient,
+            amount,
+        )
+        .await
+        .is_ok()
+        {
+            if let Some(budget) = self.budgets.get_mut(&(msg::source(), *token)) {
+                budget.epoch = epoch;
+                budget.spent = spent;
+            }
+            self.withdraw_tokens(token, amount);
+            self.transactions.remove(&current_transaction_id);
+            msg::reply(
+                DaoEvent::BudgetSpent {
+                    treasurer: msg::source(),
+                    token: *token,
+                    recipient: *recipient,
+                    amount,
+                    epoch,
+                    spent,
+                },
+                0,
+            )
+            .expect("Error in a reply `DaoEvent::BudgetSpent`");
+        } else {
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+        }
+    }
+
+    // sets the budget on the governance proposal, the zero amount removes it
+    pub fn set_budget(
+        &mut self,
+        account: &ActorId,
+        token: &ActorId,
+        amount: u128,
+        periods_per_epoch: u64,
+    ) {
+        if amount == 0 {
+            self.budgets.remove(&(*account, *token));
+            return;
+        }
+        let budget = self.budgets.entry((*account, *token)).or_insert(Budget {
+            token: *token,
+            amount,
+            periods_per_epoch,
+            epoch: 0,
+            spent: 0,
+        });
+        // the spending in the current epoch is kept if only the amount changes
+        if budget.periods_per_epoch != periods_per_epoch {
+            budget.periods_per_epoch = periods_per_epoch;
+            budget.spent = 0;
+        }
+        budget.amount = amount;
+    }
+
+    // the index of the current epoch of the budget
+    fn budget_epoch(&self, budget: &Budget) -> u64 {
+        let epoch_duration = self
+            .period_duration
+            .saturating_mul(budget.periods_per_epoch)
+            .max(1);
+        exec::block_timestamp() / epoch_duration
+    }
+}
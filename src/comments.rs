@@ -0,0 +1,50 @@
+use crate::contract::Dao;
+use dao_io::*;
+use gstd::{exec, msg, prelude::*};
+
+impl Dao {
+    pub fn comment(&mut self, proposal_id: u128, content_hash: [u8; 32]) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        let member_id = *self
+            .member_by_delegate_key
+            .get(&msg::source())
+            .expect("Account is not a delegate");
+        let now = exec::block_timestamp();
+        let proposal = self
+            .proposals
+            .get(&proposal_id)
+            .expect("proposal does not exist");
+        if proposal.processed || proposal.aborted || proposal.cancelled {
+            panic!("Proposal has already been processed, aborted or cancelled");
+        }
+        if now > proposal.voting_ends_at(self.voting_period_length) {
+            panic!("proposal voting period has expired");
+        }
+        // the member and its delegate share the limit
+        if let Some(last_comment_at) = self.last_comment_at.get(&member_id) {
+            if now < last_comment_at.saturating_add(self.period_duration) {
+                panic!("Member has commented too recently");
+            }
+        }
+        self.last_comment_at.insert(member_id, now);
+
+        let comments = self.comments.entry(proposal_id).or_default();
+        let comment_index = comments.len() as u32;
+        comments.push(ProposalComment {
+            author: msg::source(),
+            content_hash,
+            posted_at: now,
+        });
+        msg::reply(
+            DaoEvent::Commented {
+                proposal_id,
+                comment_index,
+                author: msg::source(),
+                content_hash,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::Commented`");
+    }
+}
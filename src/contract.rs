@@ -1,6 +1,6 @@
-use crate::ft_messages::*;
+use crate::{ft_messages::*, nft_messages::*};
 use dao_io::*;
-use gstd::{exec, msg, prelude::*, ActorId, String};
+use gstd::{exec, msg, prelude::*, ActorId};
 use hashbrown::HashMap;
 
 pub const BASE_PERCENT: u8 = 100;
@@ -9,8 +9,20 @@ static mut DAO: Option<Dao> = None;
 
 #[derive(Debug, Default)]
 pub struct Dao {
-    pub admin: ActorId,
+    pub admins: Vec<ActorId>,
+    pub admin_threshold: u32,
+    pub admin_operation_lifetime: u64,
+    pub admin_operation_id: u64,
+    pub admin_operations: HashMap<u64, PendingAdminOperation>,
+    pub paused: bool,
+    pub pending_admin: Option<(ActorId, ActorId)>,
+    pub roles: HashMap<ActorId, BTreeSet<Role>>,
+    pub treasurer_spending_limit: u128,
+    // the period of the last treasurer payout and the tokens paid out during it
+    pub treasurer_period: u64,
+    pub treasurer_spent: u128,
     pub approved_token_program_id: ActorId,
+    pub membership_nft: Option<ActorId>,
     pub period_duration: u64,
     pub voting_period_length: u64,
     pub grace_period_length: u64,
@@ -18,54 +30,82 @@ pub struct Dao {
     pub abort_window: u64,
     pub total_shares: u128,
     pub balance: u128,
+    pub value_balance: u128,
     pub members: HashMap<ActorId, Member>,
     pub member_by_delegate_key: HashMap<ActorId, ActorId>,
     pub proposal_id: u128,
     pub proposals: HashMap<u128, Proposal>,
-    pub whitelist: Vec<ActorId>,
+    pub lanes: HashMap<ProposalLane, u128>,
+    pub comments: HashMap<u128, Vec<ProposalComment>>,
+    // the time of the last comment by the member
+    pub last_comment_at: HashMap<ActorId, u64>,
+    pub whitelist: HashMap<ActorId, Option<u64>>,
+    pub whitelist_mode: WhitelistMode,
+    pub membership_gate: Option<MembershipGate>,
+    pub share_transfer_policy: ShareTransferPolicy,
+    pub optimistic: Option<OptimisticConfig>,
+    pub conviction: Option<ConvictionConfig>,
+    pub conviction_request_id: u128,
+    pub conviction_requests: HashMap<u128, ConvictionRequest>,
+    pub grants: HashMap<u128, Grant>,
+    pub budgets: HashMap<(ActorId, ActorId), Budget>,
+    pub token_balances: HashMap<ActorId, u128>,
     pub transaction_id: u64,
     pub transactions: HashMap<u64, Option<DaoAction>>,
 }
 
 impl Dao {
-    pub fn add_to_whitelist(&mut self, member: &ActorId) {
-        self.assert_admin();
-        Self::assert_not_zero_address(member);
-
-        if self.whitelist.contains(member) {
-            panic!("Member has already been added to the whitelist");
-        }
-        self.whitelist.push(*member);
-        msg::reply(DaoEvent::MemberAddedToWhitelist(*member), 0)
-            .expect("Error in a reply `DaoEvent::MemberAddedToWhitelist`");
-    }
-
+    #[allow(clippy::too_many_arguments)]
     pub async fn submit_membership_proposal(
         &mut self,
         transaction_id: Option<u64>,
         applicant: &ActorId,
         token_tribute: u128,
+        value_tribute: u128,
         shares_requested: u128,
         quorum: u128,
-        details: String,
+        metadata: ProposalMetadata,
+        gate_token_id: Option<NftTokenId>,
     ) {
         let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
         self.check_for_membership();
-        // check that applicant is either in whitelist or a DAO member
-        if !self.whitelist.contains(applicant) && !self.members.contains_key(applicant) {
-            panic!("Applicant must be either in whitelist or be a DAO member");
+        Self::validate_metadata(&metadata);
+        if token_tribute > 0 && value_tribute > 0 {
+            panic!("Tribute must be either in tokens or in native value");
+        }
+        // check that applicant is either in whitelist, a DAO member or passes the membership gate
+        if !self.is_whitelisted(applicant) && !self.members.contains_key(applicant) {
+            if self.membership_gate.is_none() {
+                panic!("Applicant must be either in whitelist or be a DAO member");
+            }
+            // the native value can't be kept across the gate query,
+            // since the rerun transaction doesn't carry the attached value
+            if value_tribute > 0 {
+                panic!("Native value tribute is not accepted from applicants checked by the membership gate");
+            }
+            // the gate program is queried after the transaction is saved,
+            // so the rejected applicant is reported by removing the transaction instead of panicking
+            if !self.passes_membership_gate(applicant, gate_token_id).await {
+                self.transactions.remove(&current_transaction_id);
+                msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                    .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                return;
+            }
         }
 
         // transfer applicant tokens to DAO contract
-        if transfer_tokens(
-            current_transaction_id,
-            &self.approved_token_program_id,
-            applicant,
-            &exec::program_id(),
-            token_tribute,
-        )
-        .await
-        .is_err()
+        // the native value tribute is already attached to the message
+        if token_tribute > 0
+            && transfer_tokens(
+                current_transaction_id,
+                &self.approved_token_program_id,
+                applicant,
+                &exec::program_id(),
+                token_tribute,
+            )
+            .await
+            .is_err()
         {
             self.transactions.remove(&current_transaction_id);
             msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
@@ -73,20 +113,6 @@ impl Dao {
             return;
         };
 
-        let mut starting_period = exec::block_timestamp();
-        let proposal_id = self.proposal_id;
-        // compute startingPeriod for proposal
-        // there should be a minimum time interval between proposals (period_duration) so that members have time to ragequit
-        if proposal_id > 0 {
-            let previous_starting_period = self
-                .proposals
-                .get(&(&proposal_id - 1))
-                .expect("Error getting proposal")
-                .starting_period;
-            if starting_period < previous_starting_period + self.period_duration {
-                starting_period = previous_starting_period + self.period_duration;
-            }
-        }
         let proposal = Proposal {
             proposer: msg::source(),
             applicant: *applicant,
@@ -94,12 +120,11 @@ impl Dao {
             quorum: quorum * BASE_PERCENT as u128,
             is_membership_proposal: true,
             token_tribute,
-            details,
-            starting_period,
+            value_tribute,
+            metadata,
             ..Proposal::default()
         };
-        self.proposals.insert(proposal_id, proposal);
-        self.proposal_id = self.proposal_id.saturating_add(1);
+        let proposal_id = self.insert_proposal(proposal);
         self.transactions.remove(&current_transaction_id);
         msg::reply(
             DaoEvent::SubmitMembershipProposal {
@@ -107,6 +132,7 @@ impl Dao {
                 applicant: *applicant,
                 proposal_id,
                 token_tribute,
+                value_tribute,
             },
             0,
         )
@@ -117,52 +143,57 @@ impl Dao {
         &mut self,
         applicant: &ActorId,
         amount: u128,
+        value_amount: u128,
         quorum: u128,
-        details: String,
+        metadata: ProposalMetadata,
+        optimistic: bool,
     ) {
+        self.assert_not_paused();
         self.check_for_membership();
+        Self::validate_metadata(&metadata);
         Self::assert_not_zero_address(applicant);
-
-        let mut starting_period = exec::block_timestamp();
-        let proposal_id = self.proposal_id;
-        // compute startingPeriod for proposal
-        // there should be a minimum time interval between proposals (period_duration) so that members have time to ragequit
-        if proposal_id > 0 {
-            let previous_starting_period = self
-                .proposals
-                .get(&(&proposal_id - 1))
-                .expect("Can't be None")
-                .starting_period;
-            if starting_period < previous_starting_period + self.period_duration {
-                starting_period = previous_starting_period + self.period_duration;
-            }
-        }
+        let quorum = if optimistic {
+            self.optimistic_quorum(amount, value_amount)
+        } else {
+            quorum
+        };
 
         let proposal = Proposal {
             proposer: msg::source(),
             applicant: *applicant,
             quorum,
             amount,
-            details,
-            starting_period,
+            value_amount,
+            metadata,
+            optimistic,
             ..Proposal::default()
         };
 
-        self.proposals.insert(proposal_id, proposal);
-        self.proposal_id = self.proposal_id.saturating_add(1);
+        let proposal_id = self.insert_proposal(proposal);
         msg::reply(
             DaoEvent::SubmitFundingProposal {
                 proposer: msg::source(),
                 applicant: *applicant,
                 proposal_id,
                 amount,
+                value_amount,
             },
             0,
         )
         .expect("Error in a reply `DaoEvent::SubmitFungingProposal");
     }
 
-    pub fn submit_vote(&mut self, proposal_id: u128, vote: Vote) {
+    pub fn submit_vote(&mut self, proposal_id: u128, vote: Vote, reason: Option<VoteReason>) {
+        self.assert_not_paused();
+        if let Some(VoteReason::Text(text)) = &reason {
+            if text.len() > MAX_REASON_LENGTH {
+                panic!("Vote reason is too long");
+            }
+        }
+        // the list of YES votes is kept short by dropping the ones that no longer lock shares
+        if let Some(member_id) = self.member_by_delegate_key.get(&msg::source()).copied() {
+            self.release_yes_votes(&member_id);
+        }
         let proposal = match self.proposals.get_mut(&proposal_id) {
             Some(proposal) => {
                 if exec::block_timestamp() > proposal.starting_period + self.voting_period_length {
@@ -171,15 +202,21 @@ impl Dao {
                 if exec::block_timestamp() < proposal.starting_period {
                     panic!("voting period has not started");
                 }
+                if proposal.voting_closed_at.is_some() {
+                    panic!("proposal voting has been closed");
+                }
                 if proposal
                     .votes_by_member
                     .iter()
-                    .any(|(actor_id, _vote)| msg::source().eq(actor_id))
+                    .any(|(actor_id, _vote, _reason)| msg::source().eq(actor_id))
                 {
                     panic!("account has already voted on this proposal");
                 }
-                if proposal.aborted {
-                    panic!("The proposal has been aborted");
+                if proposal.aborted || proposal.cancelled {
+                    panic!("The proposal has been aborted or cancelled");
+                }
+                if let ProposalKind::RankedChoice(_) = proposal.kind {
+                    panic!("Ranked-choice proposal takes ranked votes");
                 }
                 proposal
             }
@@ -203,48 +240,143 @@ impl Dao {
                 if self.total_shares > proposal.max_total_shares_at_yes_vote {
                     proposal.max_total_shares_at_yes_vote = self.total_shares;
                 }
-                // it is necessary to save the highest id of the proposal - must be processed for member to ragequit
-                if member.highest_index_yes_vote < proposal_id {
-                    member.highest_index_yes_vote = proposal_id;
+                // the voted shares can't be withdrawn until the proposal is processed or aborted
+                // unless the proposal is a non-binding signal
+                if !matches!(proposal.kind, ProposalKind::Signaling) {
+                    member.yes_votes.push((proposal_id, member.shares));
                 }
             }
             Vote::No => {
                 proposal.no_votes = proposal.no_votes.saturating_add(member.shares);
             }
         }
-        proposal.votes_by_member.push((msg::source(), vote.clone()));
+        // the voted shares can't be voted again by the member receiving them
+        member.votes.push((proposal_id, member.shares));
+        proposal
+            .votes_by_member
+            .push((msg::source(), vote.clone(), reason.clone()));
 
         msg::reply(
             DaoEvent::SubmitVote {
                 account: msg::source(),
                 proposal_id,
                 vote,
+                reason,
             },
             0,
         )
         .expect("Error in a reply `DaoEvent::SubmitVote`");
     }
 
+    pub fn close_voting(&mut self, proposal_id: u128) {
+        self.assert_not_paused();
+        if !self.has_role(&msg::source(), Role::ProposalProcessor) {
+            self.check_for_membership();
+        }
+        let total_shares = self.total_shares;
+        let dilution_bound = self.dilution_bound as u128;
+        let votes_pass = self
+            .proposals
+            .get(&proposal_id)
+            .map_or(false, |proposal| self.votes_pass(proposal));
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .expect("proposal does not exist");
+        if proposal.processed || proposal.aborted || proposal.cancelled {
+            panic!("Proposal has already been processed, aborted or cancelled");
+        }
+        let now = exec::block_timestamp();
+        if now < proposal.starting_period {
+            panic!("voting period has not started");
+        }
+        if now > proposal.starting_period + self.voting_period_length {
+            panic!("proposal voting period has expired");
+        }
+        if proposal.voting_closed_at.is_some() {
+            panic!("proposal voting has been closed");
+        }
+        // the applicant can abort the proposal until the abort window ends,
+        // so the grace period can't start earlier
+        if now <= proposal.starting_period + self.abort_window {
+            panic!("Proposal can't be closed before the abort window ends");
+        }
+        // the objections to the optimistic proposal are awaited until the end of the voting period
+        if proposal.optimistic {
+            panic!("Optimistic proposal can't be closed early");
+        }
+        // the runoff can change its winner until the last ballot
+        if let ProposalKind::RankedChoice(_) = proposal.kind {
+            panic!("Ranked-choice proposal can't be closed early");
+        }
+        // the outcome is decided against the current total shares
+        // and the passing proposal must also keep within the dilution bound checked on processing
+        if !proposal.is_decided(total_shares)
+            || (votes_pass
+                && proposal.max_total_shares_at_yes_vote >= dilution_bound * total_shares)
+        {
+            panic!("The outcome of the proposal is not decided yet");
+        }
+
+        proposal.voting_closed_at = Some(now);
+        msg::reply(
+            DaoEvent::VotingClosed {
+                proposal_id,
+                closed_at: now,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::VotingClosed`");
+    }
+
     pub async fn process_proposal(&mut self, transaction_id: Option<u64>, proposal_id: u128) {
         let current_transaction_id = self.get_transaction_id(transaction_id);
-        if proposal_id > 0
-            && !self
+        self.assert_not_paused();
+        if !self.has_role(&msg::source(), Role::ProposalProcessor) {
+            self.check_for_membership();
+        }
+        // the proposals are processed in order only within their lane
+        let previous_in_lane = self
+            .proposals
+            .get(&proposal_id)
+            .and_then(|proposal| proposal.previous_in_lane);
+        if let Some(previous_id) = previous_in_lane {
+            let previous = self
                 .proposals
-                .get(&(&proposal_id - 1))
-                .expect("Proposal does not exist")
-                .processed
-        {
-            panic!("Previous proposal must be processed");
+                .get(&previous_id)
+                .expect("Proposal does not exist");
+            if !previous.processed && !previous.aborted && !previous.cancelled {
+                panic!("Previous proposal in the lane must be processed");
+            }
         }
+        let free_balance = self.free_balance();
+        let payment_balance = match self
+            .proposals
+            .get(&proposal_id)
+            .map(|proposal| &proposal.kind)
+        {
+            Some(ProposalKind::Trade { payment_token, .. }) => self.token_balance(payment_token),
+            _ => 0,
+        };
+        let votes_pass = self
+            .proposals
+            .get(&proposal_id)
+            .map_or(false, |proposal| self.votes_pass(proposal));
+        let (token_receiver, token_sender) = self
+            .proposals
+            .get(&proposal_id)
+            .map_or((None, None), |proposal| {
+                self.membership_token_changes(proposal)
+            });
+        let (mut burned_token, mut minted_token_receiver) =
+            self.membership_token_updates(token_receiver, token_sender);
         let proposal = match self.proposals.get_mut(&proposal_id) {
             Some(proposal) => {
-                if proposal.processed || proposal.aborted {
-                    panic!("Proposal has already been processed or aborted");
+                if proposal.processed || proposal.aborted || proposal.cancelled {
+                    panic!("Proposal has already been processed, aborted or cancelled");
                 }
                 if exec::block_timestamp()
-                    < proposal.starting_period
-                        + self.voting_period_length
-                        + self.grace_period_length
+                    < proposal.voting_ends_at(self.voting_period_length) + self.grace_period_length
                 {
                     panic!("Proposal is not ready to be processed");
                 }
@@ -255,50 +387,139 @@ impl Dao {
             }
         };
 
-        proposal.passed = proposal.yes_votes > proposal.no_votes
-            && proposal.yes_votes * 10000 / self.total_shares >= proposal.quorum
+        proposal.passed = votes_pass
             && proposal.max_total_shares_at_yes_vote
-                < (self.dilution_bound as u128) * self.total_shares;
-        // if membership proposal has passed
-        if proposal.passed && proposal.is_membership_proposal {
-            let applicant = self.members.entry(proposal.applicant).or_insert(Member {
-                delegate_key: proposal.applicant,
-                shares: 0,
-                highest_index_yes_vote: 0,
-            });
-            applicant.shares = applicant.shares.saturating_add(proposal.shares_requested);
-            self.member_by_delegate_key
-                .entry(proposal.applicant)
-                .or_insert(proposal.applicant);
-            self.total_shares = self.total_shares.saturating_add(proposal.shares_requested);
-            self.balance = self.balance.saturating_add(proposal.token_tribute);
-        } else if proposal.is_membership_proposal
-            && transfer_tokens(
+                < (self.dilution_bound as u128) * self.total_shares
+            // the DAO must be able to pay the native value out
+            && proposal.value_amount <= self.value_balance
+            // the grant and the funding must be covered by the funds that aren't reserved for grants
+            && match proposal.kind {
+                ProposalKind::Grant(_) => proposal.amount <= free_balance,
+                ProposalKind::Standard if !proposal.is_membership_proposal => {
+                    proposal.amount <= free_balance
+                }
+                // the DAO must be able to pay for the trade
+                ProposalKind::Trade { .. } => proposal.amount <= payment_balance,
+                _ => match proposal.governance_action() {
+                    Some(GovernanceAction::Fund { amount, .. }) => *amount <= free_balance,
+                    _ => true,
+                },
+            };
+        if proposal.passed {
+            proposal.winning_option = proposal.instant_runoff();
+        }
+        // the governance proposal can't take the membership token back
+        // if the member hasn't approved the DAO for it, so the share transfer is skipped
+        let mut share_transfer_skipped = false;
+        if let (true, Some(nft_program_id), Some(token_id)) =
+            (proposal.passed, self.membership_nft, burned_token)
+        {
+            if !is_approved(&nft_program_id, token_id).await {
+                share_transfer_skipped = true;
+                burned_token = None;
+                minted_token_receiver = None;
+            }
+        }
+        // if the proposal has passed
+        // the membership tokens are updated for the accounts whose shares it changes
+        let mut membership_token = None;
+        if let (true, Some(nft_program_id)) = (proposal.passed, self.membership_nft) {
+            match update_membership_tokens(
                 current_transaction_id,
-                &self.approved_token_program_id,
-                &exec::program_id(),
-                &proposal.applicant,
-                proposal.token_tribute,
+                &nft_program_id,
+                burned_token,
+                minted_token_receiver,
             )
             .await
-            .is_err()
-        {
-            // the tokens are on the DAO balance
-            // we have to rerun that transaction to return tokens to applicant
-            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
-                .expect("Error in a reply `DaoEvent::TransactionFailed`");
-            return;
+            {
+                Ok(token_id) => membership_token = token_id,
+                Err(_) => {
+                    // we have to rerun that transaction to update the membership tokens
+                    msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                        .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                    return;
+                }
+            }
+        }
+
+        // the shares are issued below when the proposal is no longer borrowed
+        if proposal.passed && proposal.is_membership_proposal {
+            self.balance = self.balance.saturating_add(proposal.token_tribute);
+            self.value_balance = self.value_balance.saturating_add(proposal.value_tribute);
+        } else if proposal.is_membership_proposal {
+            if proposal.token_tribute > 0
+                && transfer_tokens(
+                    current_transaction_id,
+                    &self.approved_token_program_id,
+                    &exec::program_id(),
+                    &proposal.applicant,
+                    proposal.token_tribute,
+                )
+                .await
+                .is_err()
+            {
+                // the tokens are on the DAO balance
+                // we have to rerun that transaction to return tokens to applicant
+                msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                    .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                return;
+            }
+            // the native value tribute is returned to the proposer who attached it
+            Self::send_value(&proposal.proposer, proposal.value_tribute);
         }
 
+        // the grant isn't paid out right away, its recipient claims the released tokens
+        let is_grant = matches!(proposal.kind, ProposalKind::Grant(_));
+
+        let trade = match proposal.kind {
+            ProposalKind::Trade {
+                tribute_token,
+                payment_token,
+            } => Some((tribute_token, payment_token)),
+            _ => None,
+        };
+        if let Some((tribute_token, payment_token)) = trade {
+            // the payment is made if the trade has passed, otherwise the tribute is returned
+            let (token, amount) = if proposal.passed {
+                (payment_token, proposal.amount)
+            } else {
+                (tribute_token, proposal.token_tribute)
+            };
+            if amount > 0
+                && transfer_tokens(
+                    current_transaction_id,
+                    &token,
+                    &exec::program_id(),
+                    &proposal.applicant,
+                    amount,
+                )
+                .await
+                .is_err()
+            {
+                // we have to rerun that transaction to transfer tokens to applicant
+                msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                    .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                return;
+            }
+        }
+
+        // the governance proposal funds the applicant of its action
+        let (funded_applicant, funding_amount) = match proposal.governance_action() {
+            Some(GovernanceAction::Fund { applicant, amount }) => (*applicant, *amount),
+            _ => (proposal.applicant, proposal.amount),
+        };
         // if funding propoposal has passed
         if proposal.passed
             && !proposal.is_membership_proposal
+            && !is_grant
+            && trade.is_none()
+            && funding_amount > 0
             && transfer_tokens(
                 current_transaction_id,
                 &self.approved_token_program_id,
                 &exec::program_id(),
-                &proposal.applicant,
-                proposal.amount,
+                &funded_applicant,
+                funding_amount,
             )
             .await
             .is_err()
@@ -309,12 +530,66 @@ impl Dao {
                 .expect("Error in a reply `DaoEvent::TransactionFailed`");
             return;
         }
+        if proposal.passed && !proposal.is_membership_proposal && !is_grant && trade.is_none() {
+            self.balance = self.balance.saturating_sub(funding_amount);
+            Self::send_value(&proposal.applicant, proposal.value_amount);
+            self.value_balance = self.value_balance.saturating_sub(proposal.value_amount);
+        }
         proposal.processed = true;
+        let passed = proposal.passed;
+        let is_membership_proposal = proposal.is_membership_proposal;
+        let applicant = proposal.applicant;
+        let (tribute, payment, shares) = (
+            proposal.token_tribute,
+            proposal.amount,
+            proposal.shares_requested,
+        );
+        let action = match &proposal.kind {
+            ProposalKind::Governance(action) if passed => Some(action.clone()),
+            ProposalKind::RankedChoice(options) if passed => proposal
+                .winning_option
+                .and_then(|option| options.get(option as usize))
+                .and_then(|option| option.action.clone()),
+            ProposalKind::Grant(schedule) if passed => {
+                self.grants.insert(
+                    proposal_id,
+                    Grant {
+                        recipient: proposal.applicant,
+                        amount: proposal.amount,
+                        claimed: 0,
+                        schedule: schedule.clone(),
+                        cancelled: false,
+                        released_milestones: 0,
+                    },
+                );
+                None
+            }
+            _ => None,
+        };
+        if let Some(action) = action.filter(|_| !share_transfer_skipped) {
+            self.execute_governance_action(action);
+        }
+        if passed && is_membership_proposal {
+            self.mint_shares(&applicant, shares);
+        }
+        match trade {
+            Some((tribute_token, payment_token)) if passed => {
+                self.deposit_tokens(&tribute_token, tribute);
+                self.withdraw_tokens(&payment_token, payment);
+                if shares > 0 {
+                    self.mint_shares(&applicant, shares);
+                }
+            }
+            _ => {}
+        }
+        if let Some(receiver) = token_receiver {
+            self.set_membership_token(&receiver, membership_token);
+        }
         self.transactions.remove(&current_transaction_id);
         msg::reply(
             DaoEvent::ProcessProposal {
                 proposal_id,
-                passed: proposal.passed,
+                passed,
             },
             0,
         )
@@ -323,27 +598,41 @@ impl Dao {
 
     pub async fn ragequit(&mut self, transaction_id: Option<u64>, amount: u128) {
         let current_transaction_id = self.get_transaction_id(transaction_id);
-        if self.admin == msg::source() {
+        if self.admins.contains(&msg::source()) {
             panic!("admin can not ragequit");
         }
         let funds = self.redeemable_funds(amount);
+        let value_funds = self.redeemable_value(amount);
         let member = self
             .members
-            .get_mut(&msg::source())
+            .get(&msg::source())
             .expect("Account is not a DAO member");
 
         if amount > member.shares {
             panic!("unsufficient shares");
         }
 
-        let proposal_id = member.highest_index_yes_vote;
-        if !self
-            .proposals
-            .get(&proposal_id)
-            .expect("Cant be None")
-            .processed
-        {
-            panic!("cant ragequit until highest index proposal member voted YES on is processed");
+        if amount > self.unlocked_shares(member) {
+            panic!("cant ragequit the shares locked by YES votes on the pending proposals");
+        }
+
+        // the membership token is taken back and burned when the member leaves the DAO,
+        // we have to rerun that transaction if the member hasn't approved the DAO for the token
+        let membership_token = match (self.membership_nft, member.token_id) {
+            (Some(nft_program_id), Some(token_id)) if amount == member.shares => {
+                Some((nft_program_id, token_id))
+            }
+            _ => None,
+        };
+        if let Some((nft_program_id, token_id)) = membership_token {
+            if burn_membership_token(current_transaction_id, &nft_program_id, token_id)
+                .await
+                .is_err()
+            {
+                msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                    .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                return;
+            }
         }
 
         // the tokens are on the DAO balance
@@ -358,14 +647,24 @@ impl Dao {
         .await
         .is_ok()
         {
-            member.shares = member.shares.saturating_sub(amount);
+            if let Some(member) = self.members.get_mut(&msg::source()) {
+                member.shares = member.shares.saturating_sub(amount);
+                if membership_token.is_some() {
+                    member.token_id = None;
+                }
+            }
+            self.release_yes_votes(&msg::source());
             self.total_shares = self.total_shares.saturating_sub(amount);
+            Self::emit_share_transfer(&msg::source(), &ActorId::zero(), amount);
             self.balance = self.balance.saturating_sub(funds);
+            Self::send_value(&msg::source(), value_funds);
+            self.value_balance = self.value_balance.saturating_sub(value_funds);
             self.transactions.remove(&current_transaction_id);
             msg::reply(
                 DaoEvent::RageQuit {
                     member: msg::source(),
                     amount: funds,
+                    value: value_funds,
                 },
                 0,
             )
@@ -387,12 +686,15 @@ impl Dao {
             panic!("caller must be applicant");
         }
 
-        if !proposal.is_membership_proposal {
-            panic!("The proposal must be membership");
-        }
+        // the tribute of the trade proposal is paid in its own token
+        let token = match proposal.kind {
+            ProposalKind::Trade { tribute_token, .. } => tribute_token,
+            _ if proposal.is_membership_proposal => self.approved_token_program_id,
+            _ => panic!("The proposal must be membership or trade"),
+        };
 
-        if proposal.aborted {
-            panic!("Proposal has already been aborted");
+        if proposal.processed || proposal.aborted || proposal.cancelled {
+            panic!("Proposal has already been processed, aborted or cancelled");
         }
 
         if exec::block_timestamp() > proposal.starting_period + self.abort_window {
@@ -403,17 +705,20 @@ impl Dao {
 
         // if transfer of tokens  fails
         // we have to rerun the transaction to return tokens to applicant
-        if transfer_tokens(
-            current_transaction_id,
-            &self.approved_token_program_id,
-            &exec::program_id(),
-            &msg::source(),
-            amount,
-        )
-        .await
-        .is_ok()
+        if amount == 0
+            || transfer_tokens(
+                current_transaction_id,
+                &token,
+                &exec::program_id(),
+                &msg::source(),
+                amount,
+            )
+            .await
+            .is_ok()
         {
+            Self::send_value(&proposal.proposer, proposal.value_tribute);
             proposal.token_tribute = 0;
+            proposal.value_tribute = 0;
             proposal.aborted = true;
 
             msg::reply(DaoEvent::Abort(proposal_id), 0)
@@ -424,12 +729,59 @@ impl Dao {
         };
     }
 
-    pub fn set_admin(&mut self, new_admin: &ActorId) {
-        self.assert_admin();
-        Self::assert_not_zero_address(new_admin);
-        self.admin = *new_admin;
-        msg::reply(DaoEvent::AdminUpdated(*new_admin), 0)
-            .expect("Error in a reply `DaoEvent::AdminUpdated`");
+    pub async fn cancel_proposal(&mut self, transaction_id: Option<u64>, proposal_id: u128) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .expect("The proposal does not exist");
+
+        if proposal.proposer != msg::source() {
+            panic!("caller must be proposer");
+        }
+
+        if proposal.processed || proposal.aborted || proposal.cancelled {
+            panic!("Proposal has already been processed, aborted or cancelled");
+        }
+
+        if exec::block_timestamp() >= proposal.starting_period
+            && (!proposal.votes_by_member.is_empty() || !proposal.ballots.is_empty())
+        {
+            panic!("The proposal has already been voted on");
+        }
+
+        // the tribute of the trade proposal is paid in its own token
+        let token = match proposal.kind {
+            ProposalKind::Trade { tribute_token, .. } => tribute_token,
+            _ => self.approved_token_program_id,
+        };
+        let amount = proposal.token_tribute;
+
+        // the escrowed tokens are returned to the applicant who paid them
+        // we have to rerun the transaction in case of error
+        if amount > 0
+            && transfer_tokens(
+                current_transaction_id,
+                &token,
+                &exec::program_id(),
+                &proposal.applicant,
+                amount,
+            )
+            .await
+            .is_err()
+        {
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+            return;
+        }
+
+        Self::send_value(&proposal.proposer, proposal.value_tribute);
+        proposal.token_tribute = 0;
+        proposal.value_tribute = 0;
+        proposal.cancelled = true;
+        self.transactions.remove(&current_transaction_id);
+        msg::reply(DaoEvent::CancelProposal(proposal_id), 0)
+            .expect("Error in a reply `DaoEvent::CancelProposal`");
     }
 
     pub fn update_delegate_key(&mut self, new_delegate_key: &ActorId) {
@@ -467,15 +819,42 @@ impl Dao {
                     token_tribute,
                     shares_requested,
                     quorum,
-                    details,
+                    metadata,
+                    gate_token_id,
                 } => {
+                    // only token tributes are transferred asynchronously
                     self.submit_membership_proposal(
                         Some(transaction_id),
                         applicant,
                         *token_tribute,
+                        0,
+                        *shares_requested,
+                        *quorum,
+                        metadata.clone(),
+                        *gate_token_id,
+                    )
+                    .await;
+                }
+                DaoAction::SubmitTradeProposal {
+                    applicant,
+                    tribute_token,
+                    tribute_offered,
+                    payment_token,
+                    payment_requested,
+                    shares_requested,
+                    quorum,
+                    metadata,
+                } => {
+                    self.submit_trade_proposal(
+                        Some(transaction_id),
+                        applicant,
+                        tribute_token,
+                        *tribute_offered,
+                        payment_token,
+                        *payment_requested,
                         *shares_requested,
                         *quorum,
-                        details.clone(),
+                        metadata.clone(),
                     )
                     .await;
                 }
@@ -489,6 +868,33 @@ impl Dao {
                 DaoAction::Abort(proposal_id) => {
                     self.abort(Some(transaction_id), *proposal_id).await
                 }
+                DaoAction::CancelProposal(proposal_id) => {
+                    self.cancel_proposal(Some(transaction_id), *proposal_id)
+                        .await
+                }
+                DaoAction::ExecuteConvictionRequest(request_id) => {
+                    self.execute_conviction_request(Some(transaction_id), *request_id)
+                        .await
+                }
+                DaoAction::TreasurerPayout { recipient, amount } => {
+                    self.treasurer_payout(Some(transaction_id), recipient, *amount)
+                        .await
+                }
+                DaoAction::TransferShares { to, amount } => {
+                    self.transfer_shares(Some(transaction_id), to, *amount)
+                        .await
+                }
+                DaoAction::ClaimGrant(grant_id) => {
+                    self.claim_grant(Some(transaction_id), *grant_id).await
+                }
+                DaoAction::SpendBudget {
+                    token,
+                    recipient,
+                    amount,
+                } => {
+                    self.spend_budget(Some(transaction_id), token, recipient, *amount)
+                        .await
+                }
                 _ => unreachable!(),
             }
         }
@@ -498,8 +904,27 @@ impl Dao {
 impl From<&Dao> for DaoState {
     fn from(dao: &Dao) -> DaoState {
         DaoState {
-            admin: dao.admin,
+            admins: dao.admins.clone(),
+            admin_threshold: dao.admin_threshold,
+            admin_operation_lifetime: dao.admin_operation_lifetime,
+            admin_operation_id: dao.admin_operation_id,
+            admin_operations: dao
+                .admin_operations
+                .iter()
+                .map(|(key, value)| (*key, value.clone()))
+                .collect(),
+            paused: dao.paused,
+            pending_admin: dao.pending_admin,
+            roles: dao
+                .roles
+                .iter()
+                .map(|(key, value)| (*key, value.iter().copied().collect()))
+                .collect(),
+            treasurer_spending_limit: dao.treasurer_spending_limit,
+            treasurer_period: dao.treasurer_period,
+            treasurer_spent: dao.treasurer_spent,
             approved_token_program_id: dao.approved_token_program_id,
+            membership_nft: dao.membership_nft,
             period_duration: dao.period_duration,
             voting_period_length: dao.voting_period_length,
             grace_period_length: dao.grace_period_length,
@@ -507,6 +932,7 @@ impl From<&Dao> for DaoState {
             abort_window: dao.abort_window,
             total_shares: dao.total_shares,
             balance: dao.balance,
+            value_balance: dao.value_balance,
             members: dao
                 .members
                 .iter()
@@ -523,7 +949,52 @@ impl From<&Dao> for DaoState {
                 .iter()
                 .map(|(key, value)| (*key, value.clone()))
                 .collect(),
-            whitelist: dao.whitelist.clone(),
+            lanes: dao
+                .lanes
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            comments: dao
+                .comments
+                .iter()
+                .map(|(key, value)| (*key, value.clone()))
+                .collect(),
+            last_comment_at: dao
+                .last_comment_at
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            whitelist: dao
+                .whitelist
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
+            whitelist_mode: dao.whitelist_mode,
+            membership_gate: dao.membership_gate,
+            share_transfer_policy: dao.share_transfer_policy,
+            optimistic: dao.optimistic,
+            conviction: dao.conviction,
+            conviction_request_id: dao.conviction_request_id,
+            conviction_requests: dao
+                .conviction_requests
+                .iter()
+                .map(|(key, value)| (*key, value.clone()))
+                .collect(),
+            grants: dao
+                .grants
+                .iter()
+                .map(|(key, value)| (*key, value.clone()))
+                .collect(),
+            budgets: dao
+                .budgets
+                .iter()
+                .map(|((account, _), budget)| (*account, budget.clone()))
+                .collect(),
+            token_balances: dao
+                .token_balances
+                .iter()
+                .map(|(key, value)| (*key, *value))
+                .collect(),
             transaction_id: dao.transaction_id,
             transactions: dao
                 .transactions
@@ -537,9 +1008,16 @@ impl From<&Dao> for DaoState {
 #[no_mangle]
 extern "C" fn init() {
     let config: InitDao = msg::load().expect("Unable to decode InitDao");
+    if let Some(optimistic) = &config.optimistic {
+        Dao::validate_optimistic_config(optimistic);
+    }
     let mut dao = Dao {
-        admin: config.admin,
+        admins: vec![config.admin],
+        admin_threshold: 1,
+        admin_operation_lifetime: config.admin_operation_lifetime,
         approved_token_program_id: config.approved_token_program_id,
+        membership_nft: config.membership_nft,
+        optimistic: config.optimistic,
         voting_period_length: config.voting_period_length,
         period_duration: config.period_duration,
         grace_period_length: config.grace_period_length,
@@ -553,7 +1031,10 @@ extern "C" fn init() {
         Member {
             delegate_key: config.admin,
             shares: 1,
-            highest_index_yes_vote: 0,
+            yes_votes: Vec::new(),
+            votes: Vec::new(),
+            token_id: None,
+            staked: 0,
         },
     );
     dao.member_by_delegate_key
@@ -563,16 +1044,36 @@ extern "C" fn init() {
 
 #[gstd::async_main]
 async fn main() {
-    let action: DaoAction = msg::load().expect("Could not load Action");
+    let payload = msg::load_bytes().expect("Could not load the payload");
     let dao: &mut Dao = unsafe { DAO.get_or_insert(Default::default()) };
+    if dao.reply_to_token_query(&payload) {
+        return;
+    }
+    let action = DaoAction::decode(&mut payload.as_ref()).expect("Could not load Action");
     match action {
-        DaoAction::AddToWhiteList(account) => dao.add_to_whitelist(&account),
+        DaoAction::AddToWhiteList(account) => {
+            dao.execute_with_role(Role::Whitelister, AdminOperation::AddToWhiteList(account))
+        }
+        DaoAction::RemoveFromWhitelist(account) => dao.execute_with_role(
+            Role::Whitelister,
+            AdminOperation::RemoveFromWhitelist(account),
+        ),
+        DaoAction::UpdateWhitelist(update) => {
+            dao.execute_with_role(Role::Whitelister, AdminOperation::UpdateWhitelist(update))
+        }
+        DaoAction::SetWhitelistMode(mode) => {
+            dao.propose_admin_operation(AdminOperation::SetWhitelistMode(mode))
+        }
+        DaoAction::SetMembershipGate(gate) => {
+            dao.execute_with_role(Role::Whitelister, AdminOperation::SetMembershipGate(gate))
+        }
         DaoAction::SubmitMembershipProposal {
             applicant,
             token_tribute,
             shares_requested,
             quorum,
-            ref details,
+            ref metadata,
+            gate_token_id,
         } => {
             dao.transactions
                 .insert(dao.transaction_id, Some(action.clone()));
@@ -580,27 +1081,99 @@ async fn main() {
                 None,
                 &applicant,
                 token_tribute,
+                msg::value(),
                 shares_requested,
                 quorum,
-                details.to_string(),
+                metadata.clone(),
+                gate_token_id,
             )
             .await;
         }
         DaoAction::SubmitFundingProposal {
             applicant,
             amount,
+            value_amount,
             quorum,
-            details,
+            metadata,
+            optimistic,
+        } => dao.submit_funding_proposal(
+            &applicant,
+            amount,
+            value_amount,
+            quorum,
+            metadata,
+            optimistic,
+        ),
+        DaoAction::SubmitGovernanceProposal {
+            action: governance_action,
+            quorum,
+            metadata,
+        } => dao.submit_governance_proposal(governance_action, quorum, metadata),
+        DaoAction::SubmitSignalingProposal { quorum, metadata } => {
+            dao.submit_signaling_proposal(quorum, metadata)
+        }
+        DaoAction::SubmitRankedChoiceProposal {
+            options,
+            quorum,
+            metadata,
+        } => dao.submit_ranked_choice_proposal(options, quorum, metadata),
+        DaoAction::SubmitTradeProposal {
+            applicant,
+            tribute_token,
+            tribute_offered,
+            payment_token,
+            payment_requested,
+            shares_requested,
+            quorum,
+            ref metadata,
         } => {
-            dao.submit_funding_proposal(&applicant, amount, quorum, details);
+            dao.transactions
+                .insert(dao.transaction_id, Some(action.clone()));
+            dao.submit_trade_proposal(
+                None,
+                &applicant,
+                &tribute_token,
+                tribute_offered,
+                &payment_token,
+                payment_requested,
+                shares_requested,
+                quorum,
+                metadata.clone(),
+            )
+            .await;
+        }
+        DaoAction::SubmitGrantProposal {
+            applicant,
+            amount,
+            schedule,
+            quorum,
+            metadata,
+            optimistic,
+        } => dao.submit_grant_proposal(&applicant, amount, schedule, quorum, metadata, optimistic),
+        DaoAction::ClaimGrant(grant_id) => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.claim_grant(None, grant_id).await
         }
         DaoAction::ProcessProposal(proposal_id) => {
             dao.transactions.insert(dao.transaction_id, Some(action));
             dao.process_proposal(None, proposal_id).await;
         }
-        DaoAction::SubmitVote { proposal_id, vote } => {
-            dao.submit_vote(proposal_id, vote);
+        DaoAction::SubmitVote {
+            proposal_id,
+            vote,
+            reason,
+        } => {
+            dao.submit_vote(proposal_id, vote, reason);
         }
+        DaoAction::SubmitRankedVote {
+            proposal_id,
+            ranking,
+        } => dao.submit_ranked_vote(proposal_id, ranking),
+        DaoAction::CloseVoting(proposal_id) => dao.close_voting(proposal_id),
+        DaoAction::Comment {
+            proposal_id,
+            content_hash,
+        } => dao.comment(proposal_id, content_hash),
         DaoAction::RageQuit(amount) => {
             dao.transactions.insert(dao.transaction_id, Some(action));
             dao.ragequit(None, amount).await;
@@ -609,9 +1182,67 @@ async fn main() {
             dao.transactions.insert(dao.transaction_id, Some(action));
             dao.abort(None, proposal_id).await
         }
+        DaoAction::CancelProposal(proposal_id) => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.cancel_proposal(None, proposal_id).await
+        }
+        DaoAction::SubmitConvictionRequest {
+            applicant,
+            amount,
+            metadata,
+        } => dao.submit_conviction_request(&applicant, amount, metadata),
+        DaoAction::StakeConviction { request_id, amount } => {
+            dao.stake_conviction(request_id, amount)
+        }
+        DaoAction::WithdrawConviction { request_id, amount } => {
+            dao.withdraw_conviction(request_id, amount)
+        }
+        DaoAction::ExecuteConvictionRequest(request_id) => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.execute_conviction_request(None, request_id).await
+        }
         DaoAction::Continue(transaction_id) => dao.continue_transaction(transaction_id).await,
         DaoAction::UpdateDelegateKey(account) => dao.update_delegate_key(&account),
-        DaoAction::SetAdmin(account) => dao.set_admin(&account),
+        DaoAction::TransferShares { to, amount } => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.transfer_shares(None, &to, amount).await
+        }
+        DaoAction::ProposeAdmin(account) => {
+            dao.propose_admin_operation(AdminOperation::ProposeAdmin(account))
+        }
+        DaoAction::AcceptAdmin => dao.accept_admin(),
+        DaoAction::CancelAdminTransfer => dao.cancel_admin_transfer(),
+        DaoAction::SetAdminSigners { signers, threshold } => {
+            dao.propose_admin_operation(AdminOperation::SetAdminSigners { signers, threshold })
+        }
+        DaoAction::Pause => dao.execute_with_role(Role::Guardian, AdminOperation::Pause),
+        DaoAction::Unpause => dao.propose_admin_operation(AdminOperation::Unpause),
+        DaoAction::GrantRole { account, role } => {
+            dao.propose_admin_operation(AdminOperation::GrantRole { account, role })
+        }
+        DaoAction::RevokeRole { account, role } => {
+            dao.propose_admin_operation(AdminOperation::RevokeRole { account, role })
+        }
+        DaoAction::TreasurerPayout { recipient, amount } => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.treasurer_payout(None, &recipient, amount).await;
+        }
+        DaoAction::SpendBudget {
+            token,
+            recipient,
+            amount,
+        } => {
+            dao.transactions.insert(dao.transaction_id, Some(action));
+            dao.spend_budget(None, &token, &recipient, amount).await
+        }
+        DaoAction::ReleaseMilestone {
+            grant_id,
+            milestone,
+        } => dao.propose_admin_operation(AdminOperation::ReleaseMilestone {
+            grant_id,
+            milestone,
+        }),
+        DaoAction::ConfirmAdminOperation(operation_id) => dao.confirm_admin_operation(operation_id),
     }
 }
 
@@ -0,0 +1,214 @@
+use crate::{contract::Dao, ft_messages::transfer_tokens};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    pub fn submit_conviction_request(
+        &mut self,
+        applicant: &ActorId,
+        amount: u128,
+        metadata: ProposalMetadata,
+    ) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::assert_not_zero_address(applicant);
+        Self::validate_metadata(&metadata);
+        if self.conviction.is_none() {
+            panic!("Conviction voting is disabled");
+        }
+        if amount == 0 {
+            panic!("Amount must not be zero");
+        }
+
+        let request_id = self.conviction_request_id;
+        self.conviction_requests.insert(
+            request_id,
+            ConvictionRequest {
+                proposer: msg::source(),
+                applicant: *applicant,
+                amount,
+                metadata,
+                updated_at: self.conviction_period(),
+                ..ConvictionRequest::default()
+            },
+        );
+        self.conviction_request_id = self.conviction_request_id.saturating_add(1);
+        msg::reply(
+            DaoEvent::ConvictionRequestSubmitted {
+                proposer: msg::source(),
+                applicant: *applicant,
+                request_id,
+                amount,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::ConvictionRequestSubmitted`");
+    }
+
+    pub fn stake_conviction(&mut self, request_id: u128, amount: u128) {
+        self.assert_not_paused();
+        if amount == 0 {
+            panic!("Amount must not be zero");
+        }
+        // the shares are staked by the member the delegate key belongs to
+        let member_id = *self
+            .member_by_delegate_key
+            .get(&msg::source())
+            .expect("Account is not a delegate");
+        let member = self
+            .members
+            .get(&member_id)
+            .expect("Account is not a DAO member");
+        if member.staked.saturating_add(amount) > member.shares {
+            panic!("unsufficient shares");
+        }
+        self.update_conviction(request_id);
+
+        let request = self
+            .conviction_requests
+            .get_mut(&request_id)
+            .expect("Conviction request does not exist");
+        match request
+            .stakes
+            .iter_mut()
+            .find(|(member, _)| *member == member_id)
+        {
+            Some((_, staked)) => *staked = staked.saturating_add(amount),
+            None => request.stakes.push((member_id, amount)),
+        }
+        request.staked = request.staked.saturating_add(amount);
+        if let Some(member) = self.members.get_mut(&member_id) {
+            member.staked = member.staked.saturating_add(amount);
+        }
+        msg::reply(
+            DaoEvent::ConvictionStaked {
+                member: member_id,
+                request_id,
+                amount,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::ConvictionStaked`");
+    }
+
+    pub fn withdraw_conviction(&mut self, request_id: u128, amount: u128) {
+        let member_id = *self
+            .member_by_delegate_key
+            .get(&msg::source())
+            .expect("Account is not a delegate");
+        self.update_conviction(request_id);
+        let request = self
+            .conviction_requests
+            .get_mut(&request_id)
+            .expect("Conviction request does not exist");
+        let position = request
+            .stakes
+            .iter()
+            .position(|(member, staked)| *member == member_id && *staked >= amount)
+            .expect("Account has not staked enough shares on the request");
+        request.stakes[position].1 -= amount;
+        if request.stakes[position].1 == 0 {
+            request.stakes.remove(position);
+        }
+        request.staked = request.staked.saturating_sub(amount);
+        if let Some(member) = self.members.get_mut(&member_id) {
+            member.staked = member.staked.saturating_sub(amount);
+        }
+        msg::reply(
+            DaoEvent::ConvictionWithdrawn {
+                member: member_id,
+                request_id,
+                amount,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::ConvictionWithdrawn`");
+    }
+
+    pub async fn execute_conviction_request(
+        &mut self,
+        transaction_id: Option<u64>,
+        request_id: u128,
+    ) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
+        let config = self.conviction.expect("Conviction voting is disabled");
+        self.update_conviction(request_id);
+        let request = self
+            .conviction_requests
+            .get(&request_id)
+            .expect("Conviction request does not exist");
+        let threshold = config
+            .threshold(request.amount, self.free_balance(), self.total_shares)
+            .expect("Request exceeds the maximum part of the DAO balance");
+        if request.conviction < threshold {
+            panic!("Conviction has not reached the threshold");
+        }
+        let (applicant, amount) = (request.applicant, request.amount);
+
+        // the tokens are on the DAO balance
+        // we have to rerun that transaction to transfer tokens to applicant in case of error
+        if transfer_tokens(
+            current_transaction_id,
+            &self.approved_token_program_id,
+            &exec::program_id(),
+            &applicant,
+            amount,
+        )
+        .await
+        .is_ok()
+        {
+            self.balance = self.balance.saturating_sub(amount);
+            let request = self
+                .conviction_requests
+                .get_mut(&request_id)
+                .expect("Can't be None");
+            request.executed = true;
+            request.staked = 0;
+            // the staked shares are released
+            for (member, staked) in core::mem::take(&mut request.stakes) {
+                if let Some(member) = self.members.get_mut(&member) {
+                    member.staked = member.staked.saturating_sub(staked);
+                }
+            }
+            self.transactions.remove(&current_transaction_id);
+            msg::reply(
+                DaoEvent::ConvictionRequestExecuted {
+                    request_id,
+                    applicant,
+                    amount,
+                },
+                0,
+            )
+            .expect("Error in a reply `DaoEvent::ConvictionRequestExecuted`");
+        } else {
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+        }
+    }
+
+    // accumulates the conviction of the request up to the current period
+    fn update_conviction(&mut self, request_id: u128) {
+        let period = self.conviction_period();
+        let config = self.conviction;
+        let request = self
+            .conviction_requests
+            .get_mut(&request_id)
+            .expect("Conviction request does not exist");
+        if request.executed {
+            panic!("Conviction request has already been executed");
+        }
+        if let Some(config) = config {
+            request.conviction = config.accumulate(
+                request.conviction,
+                request.staked,
+                period.saturating_sub(request.updated_at),
+            );
+        }
+        request.updated_at = period;
+    }
+
+    fn conviction_period(&self) -> u64 {
+        exec::block_timestamp() / self.period_duration.max(1)
+    }
+}
@@ -29,3 +29,19 @@ pub async fn transfer_tokens(
         _ => Err(()),
     }
 }
+
+pub async fn balance_of(token_id: &ActorId, account: &ActorId) -> Option<u128> {
+    let reply = msg::send_for_reply_as::<_, FTokenEvent>(
+        *token_id,
+        FTokenAction::GetBalance(*account),
+        0,
+        0,
+    )
+    .expect("Error in sending a message `FTokenAction::GetBalance`")
+    .await;
+
+    match reply {
+        Ok(FTokenEvent::Balance(balance)) => Some(balance),
+        _ => None,
+    }
+}
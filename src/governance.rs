@@ -0,0 +1,142 @@
+use crate::contract::{Dao, BASE_PERCENT};
+use dao_io::*;
+use gstd::{msg, prelude::*};
+
+impl Dao {
+    pub fn submit_governance_proposal(
+        &mut self,
+        action: GovernanceAction,
+        quorum: u128,
+        metadata: ProposalMetadata,
+    ) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::validate_metadata(&metadata);
+        self.validate_governance_action(&action);
+
+        let proposal = Proposal {
+            proposer: msg::source(),
+            quorum: quorum * BASE_PERCENT as u128,
+            metadata,
+            kind: ProposalKind::Governance(action),
+            ..Proposal::default()
+        };
+
+        let proposal_id = self.insert_proposal(proposal);
+        msg::reply(
+            DaoEvent::SubmitGovernanceProposal {
+                proposer: msg::source(),
+                proposal_id,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitGovernanceProposal`");
+    }
+
+    pub(crate) fn validate_governance_action(&self, action: &GovernanceAction) {
+        match action {
+            GovernanceAction::GrantRole { account, .. }
+            | GovernanceAction::RevokeRole { account, .. } => {
+                Self::assert_not_zero_address(account)
+            }
+            GovernanceAction::UpdateWhitelist(update) => Self::validate_whitelist_update(update),
+            GovernanceAction::SetTreasurerSpendingLimit(_)
+            | GovernanceAction::SetWhitelistMode(_)
+            | GovernanceAction::SetMembershipGate(_) => {}
+            GovernanceAction::CancelGrant(grant_id) => match self.grants.get(grant_id) {
+                Some(grant) if grant.cancelled => panic!("Grant has already been cancelled"),
+                Some(_) => {}
+                None => panic!("Grant does not exist"),
+            },
+            GovernanceAction::ReleaseMilestone {
+                grant_id,
+                milestone,
+            } => self.validate_milestone_release(*grant_id, *milestone),
+            GovernanceAction::SetBudget {
+                account,
+                token,
+                amount,
+                periods_per_epoch,
+            } => {
+                Self::assert_not_zero_address(account);
+                Self::assert_not_zero_address(token);
+                if *amount > 0 && *periods_per_epoch == 0 {
+                    panic!("Budget epoch must not be zero");
+                }
+            }
+            GovernanceAction::SetShareTransferPolicy(policy) => {
+                if *policy == self.share_transfer_policy {
+                    panic!("Share transfer policy is already set");
+                }
+            }
+            GovernanceAction::SetOptimisticConfig(config) => {
+                if let Some(config) = config {
+                    Self::validate_optimistic_config(config);
+                }
+            }
+            GovernanceAction::SetConvictionConfig(config) => {
+                if let Some(config) = config {
+                    if config.decay >= CONVICTION_BASE
+                        || config.max_ratio == 0
+                        || config.max_ratio > CONVICTION_BASE
+                        || config.weight == 0
+                    {
+                        panic!("Invalid conviction config");
+                    }
+                }
+            }
+            GovernanceAction::TransferShares { from, to, amount } => {
+                if self.share_transfer_policy != ShareTransferPolicy::RequiresVote {
+                    panic!("Share transfers don't require a governance proposal");
+                }
+                // only the member or its delegate can propose to transfer the member's shares
+                if self.member_by_delegate_key.get(&msg::source()) != Some(from) {
+                    panic!("msg::source() must be the member or its delegate");
+                }
+                self.validate_share_transfer(from, to, *amount);
+            }
+            GovernanceAction::Fund { applicant, amount } => {
+                Self::assert_not_zero_address(applicant);
+                if *amount == 0 {
+                    panic!("Funding amount must not be zero");
+                }
+            }
+        }
+    }
+
+    // executes the action of the passed governance proposal
+    // it must not panic, otherwise the proposal queue gets stuck
+    pub fn execute_governance_action(&mut self, action: GovernanceAction) {
+        match action {
+            GovernanceAction::GrantRole { account, role } => self.grant_role(&account, role),
+            GovernanceAction::RevokeRole { account, role } => self.revoke_role(&account, role),
+            GovernanceAction::SetTreasurerSpendingLimit(limit) => {
+                self.treasurer_spending_limit = limit
+            }
+            GovernanceAction::UpdateWhitelist(update) => self.apply_whitelist_update(&update),
+            GovernanceAction::SetWhitelistMode(mode) => self.whitelist_mode = mode,
+            GovernanceAction::SetMembershipGate(gate) => self.membership_gate = gate,
+            GovernanceAction::CancelGrant(grant_id) => self.cancel_grant(grant_id),
+            GovernanceAction::ReleaseMilestone {
+                grant_id,
+                milestone,
+            } => {
+                self.release_milestone(grant_id, milestone);
+            }
+            GovernanceAction::SetBudget {
+                account,
+                token,
+                amount,
+                periods_per_epoch,
+            } => self.set_budget(&account, &token, amount, periods_per_epoch),
+            GovernanceAction::SetShareTransferPolicy(policy) => self.share_transfer_policy = policy,
+            GovernanceAction::SetOptimisticConfig(config) => self.optimistic = config,
+            GovernanceAction::SetConvictionConfig(config) => self.conviction = config,
+            GovernanceAction::TransferShares { from, to, amount } => {
+                self.execute_share_transfer(&from, &to, amount)
+            }
+            // the tokens are transferred asynchronously while the proposal is processed
+            GovernanceAction::Fund { .. } => {}
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{contract::Dao, ft_messages::transfer_tokens};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    pub fn submit_grant_proposal(
+        &mut self,
+        applicant: &ActorId,
+        amount: u128,
+        schedule: GrantSchedule,
+        quorum: u128,
+        metadata: ProposalMetadata,
+        optimistic: bool,
+    ) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::validate_metadata(&metadata);
+        Self::assert_not_zero_address(applicant);
+        if amount == 0 {
+            panic!("Grant amount must not be zero");
+        }
+        match &schedule {
+            GrantSchedule::Vesting(vesting) => {
+                if vesting.duration == 0 || vesting.cliff > vesting.duration {
+                    panic!("Invalid vesting schedule");
+                }
+            }
+            GrantSchedule::Milestones(milestones) => {
+                if milestones.len() > MAX_MILESTONES {
+                    panic!("Grant can't have more than {MAX_MILESTONES} milestones");
+                }
+                if milestones
+                    .iter()
+                    .any(|milestone| milestone.description.len() > MAX_TITLE_LENGTH)
+                {
+                    panic!("Milestone description is too long");
+                }
+                if milestones.iter().any(|milestone| milestone.amount == 0) {
+                    panic!("Milestone amount must not be zero");
+                }
+                let total = milestones.iter().fold(0u128, |total, milestone| {
+                    total.saturating_add(milestone.amount)
+                });
+                if total != amount {
+                    panic!("Milestone amounts must add up to the grant amount");
+                }
+            }
+            GrantSchedule::Recurring(recurring) => {
+                let payments = match recurring.payments() {
+                    Some(payments)
+                        if payments > 0 && recurring.payment > 0 && recurring.interval > 0 =>
+                    {
+                        payments
+                    }
+                    _ => panic!("Invalid recurring schedule"),
+                };
+                if recurring.payment.saturating_mul(payments as u128) != amount {
+                    panic!("Recurring payments must add up to the grant amount");
+                }
+            }
+        }
+        let quorum = if optimistic {
+            self.optimistic_quorum(amount, 0)
+        } else {
+            quorum
+        };
+
+        let proposal = Proposal {
+            proposer: msg::source(),
+            applicant: *applicant,
+            quorum,
+            amount,
+            metadata,
+            kind: ProposalKind::Grant(schedule),
+            optimistic,
+            ..Proposal::default()
+        };
+
+        let proposal_id = self.insert_proposal(proposal);
+        msg::reply(
+            DaoEvent::SubmitGrantProposal {
+                proposer: msg::source(),
+                applicant: *applicant,
+                proposal_id,
+                amount,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitGrantProposal`");
+    }
+
+    pub async fn claim_grant(&mut self, transaction_id: Option<u64>, grant_id: u128) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        // the payouts from the DAO balance stop while the DAO is paused
+        self.assert_not_paused();
+        let grant = self.grants.get(&grant_id).expect("Grant does not exist");
+        // anyone can trigger the due recurring payments
+        if grant.recipient != msg::source()
+            && !matches!(grant.schedule, GrantSchedule::Recurring(_))
+        {
+            panic!("msg::source() must be the grant recipient");
+        }
+        let recipient = grant.recipient;
+        let amount = grant
+            .vested(exec::block_timestamp())
+            .saturating_sub(grant.claimed);
+        if amount == 0 {
+            panic!("Nothing to claim");
+        }
+
+        // the tokens are on the DAO balance
+        // we have to rerun that transaction to transfer tokens to recipient in case of error
+        if transfer_tokens(
+            current_transaction_id,
+            &self.approved_token_program_id,
+            &exec::program_id(),
+            &recipient,
+            amount,
+        )
+        .await
+        .is_ok()
+        {
+            let grant = self.grants.get_mut(&grant_id).expect("Can't be None");
+            grant.claimed = grant.claimed.saturating_add(amount);
+            self.balance = self.balance.saturating_sub(amount);
+            self.transactions.remove(&current_transaction_id);
+            msg::reply(
+                DaoEvent::GrantClaimed {
+                    grant_id,
+                    recipient,
+                    amount,
+                },
+                0,
+            )
+            .expect("Error in a reply `DaoEvent::GrantClaimed`");
+        } else {
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+        }
+    }
+
+    // freezes the grant at the released amount, the rest returns to the free DAO balance
+    pub fn cancel_grant(&mut self, grant_id: u128) {
+        if let Some(grant) = self.grants.get_mut(&grant_id) {
+            if !grant.cancelled {
+                grant.amount = grant.vested(exec::block_timestamp());
+                grant.cancelled = true;
+            }
+        }
+    }
+
+    pub fn validate_milestone_release(&self, grant_id: u128, milestone: u32) {
+        let grant = self.grants.get(&grant_id).expect("Grant does not exist");
+        if grant.cancelled {
+            panic!("Grant has been cancelled");
+        }
+        let milestones = match &grant.schedule {
+            GrantSchedule::Milestones(milestones) => milestones,
+            _ => panic!("Grant is not paid by milestones"),
+        };
+        if milestone != grant.released_milestones || milestone as usize >= milestones.len() {
+            panic!("Milestone must be the next unreleased one");
+        }
+    }
+
+    // releases the milestone validated by `validate_milestone_release`
+    // it returns the released amount and doesn't panic if the milestone can't be released
+    pub fn release_milestone(&mut self, grant_id: u128, milestone: u32) -> u128 {
+        let Some(grant) = self.grants.get_mut(&grant_id) else {
+            return 0;
+        };
+        let amount = match &grant.schedule {
+            GrantSchedule::Milestones(milestones)
+                if !grant.cancelled && milestone == grant.released_milestones =>
+            {
+                milestones
+                    .get(milestone as usize)
+                    .map_or(0, |milestone| milestone.amount)
+            }
+            _ => 0,
+        };
+        if amount > 0 {
+            grant.released_milestones += 1;
+        }
+        amount
+    }
+
+    // the tokens reserved for grants that haven't been claimed yet
+    pub fn grants_liability(&self) -> u128 {
+        self.grants
+            .values()
+            .map(|grant| grant.amount.saturating_sub(grant.claimed))
+            .fold(0, u128::saturating_add)
+    }
+
+    // the DAO balance that isn't reserved for grants
+    pub fn free_balance(&self) -> u128 {
+        self.balance.saturating_sub(self.grants_liability())
+    }
+}
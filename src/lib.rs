@@ -1,11 +1,39 @@
 #![no_std]
 
+#[cfg(not(feature = "binary-vendor"))]
+pub mod admin;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod budgets;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod comments;
 #[cfg(not(feature = "binary-vendor"))]
 pub mod contract;
 #[cfg(not(feature = "binary-vendor"))]
+pub mod conviction;
+#[cfg(not(feature = "binary-vendor"))]
 pub mod ft_messages;
 #[cfg(not(feature = "binary-vendor"))]
+pub mod governance;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod grants;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod nft_messages;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod optimistic;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod ranked;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod roles;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod shares;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod signaling;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod trade;
+#[cfg(not(feature = "binary-vendor"))]
 pub mod utils;
+#[cfg(not(feature = "binary-vendor"))]
+pub mod whitelist;
 
 // See `Cargo.toml` for the description of the "binary-vendor" feature.
 #[cfg(feature = "binary-vendor")]
@@ -0,0 +1,152 @@
+use dao_io::NftTokenId;
+use gear_lib::non_fungible_token::token::TokenMetadata;
+use gstd::{exec, msg, prelude::*, ActorId};
+use nft_io::{NFTAction, NFTEvent};
+use primitive_types::U256;
+
+// the NFT program dedups messages by transaction ID,
+// so each DAO transaction uses four IDs: two for burning and two for minting
+fn nft_transaction_id(transaction_id: u64, step: u64) -> u64 {
+    transaction_id.wrapping_mul(4).wrapping_add(step)
+}
+
+async fn transfer_token(
+    transaction_id: u64,
+    nft_program_id: &ActorId,
+    to: &ActorId,
+    token_id: NftTokenId,
+) -> Result<(), ()> {
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program_id,
+        NFTAction::Transfer {
+            transaction_id,
+            to: *to,
+            token_id: U256(token_id),
+        },
+        0,
+        0,
+    )
+    .expect("Error in sending a message `NFTAction::Transfer`")
+    .await;
+    match reply {
+        Ok(NFTEvent::Transfer(_)) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+// mints the membership token and transfers it to the member
+pub async fn mint_membership_token(
+    transaction_id: u64,
+    nft_program_id: &ActorId,
+    member: &ActorId,
+) -> Result<NftTokenId, ()> {
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program_id,
+        NFTAction::Mint {
+            transaction_id: nft_transaction_id(transaction_id, 2),
+            token_metadata: TokenMetadata {
+                name: String::from("DAO membership"),
+                ..TokenMetadata::default()
+            },
+        },
+        0,
+        0,
+    )
+    .expect("Error in sending a message `NFTAction::Mint`")
+    .await;
+    let token_id = match reply {
+        Ok(NFTEvent::Transfer(transfer)) => transfer.token_id.0,
+        _ => return Err(()),
+    };
+    transfer_token(
+        nft_transaction_id(transaction_id, 3),
+        nft_program_id,
+        member,
+        token_id,
+    )
+    .await?;
+    Ok(token_id)
+}
+
+// takes the membership token back from the member and burns it,
+// the member must have approved the DAO for the token
+pub async fn burn_membership_token(
+    transaction_id: u64,
+    nft_program_id: &ActorId,
+    token_id: NftTokenId,
+) -> Result<(), ()> {
+    transfer_token(
+        nft_transaction_id(transaction_id, 0),
+        nft_program_id,
+        &exec::program_id(),
+        token_id,
+    )
+    .await?;
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program_id,
+        NFTAction::Burn {
+            transaction_id: nft_transaction_id(transaction_id, 1),
+            token_id: U256(token_id),
+        },
+        0,
+        0,
+    )
+    .expect("Error in sending a message `NFTAction::Burn`")
+    .await;
+    match reply {
+        Ok(NFTEvent::Transfer(_)) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+// burns the token of the account left without shares
+// and mints the token for the account receiving its first shares, returns the minted token
+pub async fn update_membership_tokens(
+    transaction_id: u64,
+    nft_program_id: &ActorId,
+    burned_token: Option<NftTokenId>,
+    receiver: Option<ActorId>,
+) -> Result<Option<NftTokenId>, ()> {
+    if let Some(token_id) = burned_token {
+        burn_membership_token(transaction_id, nft_program_id, token_id).await?;
+    }
+    match receiver {
+        Some(receiver) => mint_membership_token(transaction_id, nft_program_id, &receiver)
+            .await
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+// checks that the DAO can take the membership token back from the member
+pub async fn is_approved(nft_program_id: &ActorId, token_id: NftTokenId) -> bool {
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program_id,
+        NFTAction::IsApproved {
+            to: exec::program_id(),
+            token_id: U256(token_id),
+        },
+        0,
+        0,
+    )
+    .expect("Error in sending a message `NFTAction::IsApproved`")
+    .await;
+    matches!(reply, Ok(NFTEvent::IsApproved { approved: true, .. }))
+}
+
+pub async fn owner_of(nft_program_id: &ActorId, token_id: NftTokenId) -> Option<ActorId> {
+    let reply = msg::send_for_reply_as::<_, NFTEvent>(
+        *nft_program_id,
+        NFTAction::Owner {
+            token_id: U256(token_id),
+        },
+        0,
+        0,
+    )
+    .expect("Error in sending a message `NFTAction::Owner`")
+    .await;
+    match reply {
+        Ok(NFTEvent::Owner { owner, .. }) => Some(owner),
+        _ => None,
+    }
+}
@@ -0,0 +1,47 @@
+use crate::contract::{Dao, BASE_PERCENT};
+use dao_io::*;
+
+impl Dao {
+    // checks that the payment can be proposed optimistically and returns the quorum of the proposal
+    pub fn optimistic_quorum(&self, amount: u128, value_amount: u128) -> u128 {
+        let config = self
+            .optimistic
+            .as_ref()
+            .expect("Optimistic proposals are disabled");
+        if amount > config.amount_cap || value_amount > config.value_amount_cap {
+            panic!("Amount exceeds the cap of optimistic proposals");
+        }
+        config.quorum * BASE_PERCENT as u128
+    }
+
+    pub fn validate_optimistic_config(config: &OptimisticConfig) {
+        if config.objection_threshold > 100 || config.quorum > 100 {
+            panic!("Objection threshold and quorum must not exceed 100 percent");
+        }
+    }
+
+    // the optimistic proposal passes unless the NO votes exceed the objection threshold,
+    // it falls back to the usual rule if optimistic proposals have been disabled
+    pub fn votes_pass(&self, proposal: &Proposal) -> bool {
+        // the ranked-choice proposal needs the quorum of the ballots and the runoff winner
+        if let ProposalKind::RankedChoice(_) = proposal.kind {
+            let ballot_shares = proposal
+                .ballots
+                .iter()
+                .fold(0, |total: u128, ballot| total.saturating_add(ballot.shares));
+            return ballot_shares * 10000 / self.total_shares >= proposal.quorum
+                && proposal.instant_runoff().is_some();
+        }
+        let meets_quorum = |votes: u128| votes * 10000 / self.total_shares >= proposal.quorum;
+        match self.optimistic {
+            // nobody has to vote YES on the optimistic proposal,
+            // so its quorum counts the shares voted either way
+            Some(config) if proposal.optimistic => {
+                proposal.no_votes * 10000 / self.total_shares
+                    <= config.objection_threshold * BASE_PERCENT as u128
+                    && meets_quorum(proposal.yes_votes.saturating_add(proposal.no_votes))
+            }
+            _ => proposal.yes_votes > proposal.no_votes && meets_quorum(proposal.yes_votes),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::contract::{Dao, BASE_PERCENT};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*};
+
+/// The maximum number of options of the ranked-choice proposal.
+pub const MAX_OPTIONS: usize = 16;
+
+impl Dao {
+    pub fn submit_ranked_choice_proposal(
+        &mut self,
+        options: Vec<ProposalOption>,
+        quorum: u128,
+        metadata: ProposalMetadata,
+    ) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::validate_metadata(&metadata);
+        if options.len() < 2 || options.len() > MAX_OPTIONS {
+            panic!("Ranked-choice proposal must have from 2 to {MAX_OPTIONS} options");
+        }
+        for option in &options {
+            if option.description.len() > MAX_TITLE_LENGTH {
+                panic!("Option description is too long");
+            }
+            if let Some(action) = &option.action {
+                self.validate_governance_action(action);
+            }
+        }
+
+        let proposal = Proposal {
+            proposer: msg::source(),
+            quorum: quorum * BASE_PERCENT as u128,
+            metadata,
+            kind: ProposalKind::RankedChoice(options),
+            ..Proposal::default()
+        };
+
+        let proposal_id = self.insert_proposal(proposal);
+        msg::reply(
+            DaoEvent::SubmitRankedChoiceProposal {
+                proposer: msg::source(),
+                proposal_id,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitRankedChoiceProposal`");
+    }
+
+    pub fn submit_ranked_vote(&mut self, proposal_id: u128, ranking: Vec<u32>) {
+        self.assert_not_paused();
+        // the list of YES votes is kept short by dropping the ones that no longer lock shares
+        if let Some(member_id) = self.member_by_delegate_key.get(&msg::source()).copied() {
+            self.release_yes_votes(&member_id);
+        }
+        let member_id = *self
+            .member_by_delegate_key
+            .get(&msg::source())
+            .expect("Account is not a delegate");
+        let total_shares = self.total_shares;
+        let voting_period_length = self.voting_period_length;
+        let proposal = self
+            .proposals
+            .get_mut(&proposal_id)
+            .expect("proposal does not exist");
+        let ProposalKind::RankedChoice(options) = &proposal.kind else {
+            panic!("Proposal is not ranked-choice");
+        };
+        if ranking.is_empty() {
+            panic!("Ranking must not be empty");
+        }
+        for (position, option) in ranking.iter().enumerate() {
+            if *option as usize >= options.len() {
+                panic!("Option does not exist");
+            }
+            if ranking[..position].contains(option) {
+                panic!("Option is ranked more than once");
+            }
+        }
+        if exec::block_timestamp() > proposal.starting_period + voting_period_length {
+            panic!("proposal voting period has expired");
+        }
+        if exec::block_timestamp() < proposal.starting_period {
+            panic!("voting period has not started");
+        }
+        if proposal.aborted || proposal.cancelled {
+            panic!("The proposal has been aborted or cancelled");
+        }
+        if proposal
+            .ballots
+            .iter()
+            .any(|ballot| ballot.voter == msg::source())
+        {
+            panic!("account has already voted on this proposal");
+        }
+
+        let member = self
+            .members
+            .get_mut(&member_id)
+            .expect("Account is not a DAO member");
+        proposal.ballots.push(RankedBallot {
+            voter: msg::source(),
+            ranking: ranking.clone(),
+            shares: member.shares,
+        });
+        if total_shares > proposal.max_total_shares_at_yes_vote {
+            proposal.max_total_shares_at_yes_vote = total_shares;
+        }
+        // the ranked shares are locked as the YES votes until the proposal is processed or aborted
+        member.yes_votes.push((proposal_id, member.shares));
+        member.votes.push((proposal_id, member.shares));
+
+        msg::reply(
+            DaoEvent::SubmitRankedVote {
+                account: msg::source(),
+                proposal_id,
+                ranking,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitRankedVote`");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{contract::Dao, ft_messages::transfer_tokens};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    // executes the operation right away if `msg::source()` has the role,
+    // otherwise proposes it to admin signers
+    pub fn execute_with_role(&mut self, role: Role, operation: AdminOperation) {
+        if !self.has_role(&msg::source(), role) {
+            self.propose_admin_operation(operation);
+            return;
+        }
+        self.validate_admin_operation(&operation);
+        let event = self.execute_admin_operation(operation, &msg::source());
+        msg::reply(event, 0).expect("Error in a reply `DaoEvent`");
+    }
+
+    pub fn grant_role(&mut self, account: &ActorId, role: Role) {
+        self.roles.entry(*account).or_default().insert(role);
+    }
+
+    pub fn revoke_role(&mut self, account: &ActorId, role: Role) {
+        if let Some(roles) = self.roles.get_mut(account) {
+            roles.remove(&role);
+            if roles.is_empty() {
+                self.roles.remove(account);
+            }
+        }
+    }
+
+    pub async fn treasurer_payout(
+        &mut self,
+        transaction_id: Option<u64>,
+        recipient: &ActorId,
+        amount: u128,
+    ) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
+        if !self.has_role(&msg::source(), Role::Treasurer) {
+            panic!("msg::source() must be DAO treasurer");
+        }
+        Self::assert_not_zero_address(recipient);
+        let period = exec::block_timestamp() / self.period_duration.max(1);
+        let spent = if self.treasurer_period == period {
+            self.treasurer_spent
+        } else {
+            0
+        }
+        .saturating_add(amount);
+        if spent > self.treasurer_spending_limit {
+            panic!("Amount exceeds the treasurer spending limit left for the period");
+        }
+        if amount > self.free_balance() {
+            panic!("Insufficient DAO balance");
+        }
+
+        // the tokens are on the DAO balance
+        // we have to rerun that transaction to transfer tokens to recipient in case of error
+        if transfer_tokens(
+            current_transaction_id,
+            &self.approved_token_program_id,
+            &exec::program_id(),
+            recipient,
+            amount,
+        )
+        .await
+        .is_ok()
+        {
+            self.balance = self.balance.saturating_sub(amount);
+            self.treasurer_period = period;
+            self.treasurer_spent = spent;
+            self.transactions.remove(&current_transaction_id);
+            msg::reply(
+                DaoEvent::TreasurerPayout {
+                    treasurer: msg::source(),
+                    recipient: *recipient,
+                    amount,
+                },
+                0,
+            )
+            .expect("Error in a reply `DaoEvent::TreasurerPayout`");
+        } else {
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+        }
+    }
+}
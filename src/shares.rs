@@ -0,0 +1,170 @@
+use crate::{contract::Dao, nft_messages::*};
+use dao_io::*;
+use gstd::{msg, prelude::*, ActorId};
+
+impl Dao {
+    pub async fn transfer_shares(
+        &mut self,
+        transaction_id: Option<u64>,
+        to: &ActorId,
+        amount: u128,
+    ) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
+        let from = msg::source();
+        self.validate_share_transfer(&from, to, amount);
+        match self.share_transfer_policy {
+            ShareTransferPolicy::Disabled => panic!("Share transfers are disabled"),
+            ShareTransferPolicy::MembersOnly => {
+                if !self.is_member(to) {
+                    panic!("Recipient must be a DAO member");
+                }
+            }
+            ShareTransferPolicy::Whitelisted => {
+                if !self.is_member(to) && !self.is_whitelisted(to) {
+                    panic!("Recipient must be either in whitelist or be a DAO member");
+                }
+            }
+            ShareTransferPolicy::RequiresVote => {
+                panic!("Share transfers require a governance proposal")
+            }
+        }
+
+        // we have to rerun that transaction to update the membership tokens in case of error,
+        // e.g. after the sender approves the DAO to take its token back
+        let mut membership_token = None;
+        if let Some(nft_program_id) = self.membership_nft {
+            let (burned_token, token_receiver) =
+                self.membership_token_updates(Some(*to), Some((from, amount)));
+            match update_membership_tokens(
+                current_transaction_id,
+                &nft_program_id,
+                burned_token,
+                token_receiver,
+            )
+            .await
+            {
+                Ok(token_id) => membership_token = token_id,
+                Err(_) => {
+                    msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                        .expect("Error in a reply `DaoEvent::TransactionFailed`");
+                    return;
+                }
+            }
+        }
+
+        self.move_shares(&from, to, amount);
+        self.set_membership_token(to, membership_token);
+        self.transactions.remove(&current_transaction_id);
+        msg::reply(
+            DaoEvent::SharesTransferred {
+                from,
+                to: *to,
+                amount,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SharesTransferred`");
+    }
+
+    pub fn validate_share_transfer(&self, from: &ActorId, to: &ActorId, amount: u128) {
+        Self::assert_not_zero_address(to);
+        if from == to {
+            panic!("Shares can't be transferred to the member itself");
+        }
+        if amount == 0 {
+            panic!("Amount must not be zero");
+        }
+        let member = self.members.get(from).expect("Account is not a DAO member");
+        if amount > member.shares {
+            panic!("unsufficient shares");
+        }
+        if amount > self.transferable_shares(member) {
+            panic!("cant transfer the shares voted on the pending proposals");
+        }
+    }
+
+    // transfers the shares approved by the governance proposal
+    // it must not panic since it's executed in `process_proposal`
+    pub fn execute_share_transfer(&mut self, from: &ActorId, to: &ActorId, amount: u128) {
+        if self.share_transfer_allowed(from, to, amount) {
+            self.move_shares(from, to, amount);
+        }
+    }
+
+    pub fn share_transfer_allowed(&self, from: &ActorId, to: &ActorId, amount: u128) -> bool {
+        match self.members.get(from) {
+            Some(member) => {
+                amount <= self.transferable_shares(member) && !to.is_zero() && from != to
+            }
+            None => false,
+        }
+    }
+
+    // the membership token follows the shares:
+    // the account receiving its first shares gets a token and the account left without shares loses it,
+    // returns the token to burn and the account to mint a token for
+    pub fn membership_token_updates(
+        &self,
+        receiver: Option<ActorId>,
+        sender: Option<(ActorId, u128)>,
+    ) -> (Option<NftTokenId>, Option<ActorId>) {
+        let burned_token = sender.and_then(|(sender, amount)| {
+            self.members
+                .get(&sender)
+                .filter(|member| amount >= member.shares)
+                .and_then(|member| member.token_id)
+        });
+        let receiver = receiver.filter(|receiver| {
+            self.members
+                .get(receiver)
+                .map_or(true, |member| member.token_id.is_none())
+        });
+        (burned_token, receiver)
+    }
+
+    // the accounts whose shares the proposal changes if it passes:
+    // the one receiving the shares and the one transferring them
+    pub fn membership_token_changes(
+        &self,
+        proposal: &Proposal,
+    ) -> (Option<ActorId>, Option<(ActorId, u128)>) {
+        match (proposal.governance_action(), &proposal.kind) {
+            (Some(GovernanceAction::TransferShares { from, to, amount }), _) => {
+                if self.share_transfer_allowed(from, to, *amount) {
+                    (Some(*to), Some((*from, *amount)))
+                } else {
+                    (None, None)
+                }
+            }
+            (_, ProposalKind::Trade { .. }) if proposal.shares_requested > 0 => {
+                (Some(proposal.applicant), None)
+            }
+            _ if proposal.is_membership_proposal => (Some(proposal.applicant), None),
+            _ => (None, None),
+        }
+    }
+
+    // records the token minted for the account if the account has received the shares
+    pub fn set_membership_token(&mut self, account: &ActorId, token_id: Option<NftTokenId>) {
+        if let (Some(member), Some(token_id)) = (self.members.get_mut(account), token_id) {
+            if member.shares > 0 {
+                member.token_id = Some(token_id);
+            }
+        }
+    }
+
+    fn move_shares(&mut self, from: &ActorId, to: &ActorId, amount: u128) {
+        if let Some(member) = self.members.get_mut(from) {
+            member.shares = member.shares.saturating_sub(amount);
+            // the membership token has been burned along with the last shares
+            if member.shares == 0 {
+                member.token_id = None;
+            }
+        }
+        self.release_yes_votes(from);
+        self.total_shares = self.total_shares.saturating_sub(amount);
+        self.add_member_shares(to, amount);
+        Self::emit_share_transfer(from, to, amount);
+    }
+}
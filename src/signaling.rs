@@ -0,0 +1,29 @@
+use crate::contract::{Dao, BASE_PERCENT};
+use dao_io::*;
+use gstd::{msg, prelude::*};
+
+impl Dao {
+    pub fn submit_signaling_proposal(&mut self, quorum: u128, metadata: ProposalMetadata) {
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::validate_metadata(&metadata);
+
+        let proposal = Proposal {
+            proposer: msg::source(),
+            quorum: quorum * BASE_PERCENT as u128,
+            metadata,
+            kind: ProposalKind::Signaling,
+            ..Proposal::default()
+        };
+
+        let proposal_id = self.insert_proposal(proposal);
+        msg::reply(
+            DaoEvent::SubmitSignalingProposal {
+                proposer: msg::source(),
+                proposal_id,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitSignalingProposal`");
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    contract::{Dao, BASE_PERCENT},
+    ft_messages::transfer_tokens,
+};
+use dao_io::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+impl Dao {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_trade_proposal(
+        &mut self,
+        transaction_id: Option<u64>,
+        applicant: &ActorId,
+        tribute_token: &ActorId,
+        tribute_offered: u128,
+        payment_token: &ActorId,
+        payment_requested: u128,
+        shares_requested: u128,
+        quorum: u128,
+        metadata: ProposalMetadata,
+    ) {
+        let current_transaction_id = self.get_transaction_id(transaction_id);
+        self.assert_not_paused();
+        self.check_for_membership();
+        Self::validate_metadata(&metadata);
+        Self::assert_not_zero_address(applicant);
+        Self::assert_not_zero_address(tribute_token);
+        Self::assert_not_zero_address(payment_token);
+        if tribute_offered == 0 && payment_requested == 0 && shares_requested == 0 {
+            panic!("Trade proposal must offer or request something");
+        }
+        // the shares are issued only to the whitelisted applicants as in the membership proposal
+        if shares_requested > 0
+            && !self.is_whitelisted(applicant)
+            && !self.members.contains_key(applicant)
+        {
+            panic!("Applicant must be either in whitelist or be a DAO member");
+        }
+
+        // the tribute is held by the DAO until the proposal is processed
+        if tribute_offered > 0
+            && transfer_tokens(
+                current_transaction_id,
+                tribute_token,
+                applicant,
+                &exec::program_id(),
+                tribute_offered,
+            )
+            .await
+            .is_err()
+        {
+            self.transactions.remove(&current_transaction_id);
+            msg::reply(DaoEvent::TransactionFailed(current_transaction_id), 0)
+                .expect("Error in a reply `DaoEvent::TransactionFailed`");
+            return;
+        };
+
+        let proposal = Proposal {
+            proposer: msg::source(),
+            applicant: *applicant,
+            shares_requested,
+            quorum: quorum * BASE_PERCENT as u128,
+            amount: payment_requested,
+            token_tribute: tribute_offered,
+            metadata,
+            kind: ProposalKind::Trade {
+                tribute_token: *tribute_token,
+                payment_token: *payment_token,
+            },
+            ..Proposal::default()
+        };
+        let proposal_id = self.insert_proposal(proposal);
+        self.transactions.remove(&current_transaction_id);
+        msg::reply(
+            DaoEvent::SubmitTradeProposal {
+                proposer: msg::source(),
+                applicant: *applicant,
+                proposal_id,
+                tribute_offered,
+                payment_requested,
+                shares_requested,
+            },
+            0,
+        )
+        .expect("Error in a reply `DaoEvent::SubmitTradeProposal`");
+    }
+
+    // the number of tokens that the DAO can pay out
+    pub fn token_balance(&self, token: &ActorId) -> u128 {
+        if *token == self.approved_token_program_id {
+            self.free_balance()
+        } else {
+            self.token_balances.get(token).copied().unwrap_or_default()
+        }
+    }
+
+    pub fn deposit_tokens(&mut self, token: &ActorId, amount: u128) {
+        if *token == self.approved_token_program_id {
+            self.balance = self.balance.saturating_add(amount);
+        } else if amount > 0 {
+            let balance = self.token_balances.entry(*token).or_default();
+            *balance = balance.saturating_add(amount);
+        }
+    }
+
+    pub fn withdraw_tokens(&mut self, token: &ActorId, amount: u128) {
+        if *token == self.approved_token_program_id {
+            self.balance = self.balance.saturating_sub(amount);
+        } else if let Some(balance) = self.token_balances.get_mut(token) {
+            *balance = balance.saturating_sub(amount);
+            if *balance == 0 {
+                self.token_balances.remove(token);
+            }
+        }
+    }
+}
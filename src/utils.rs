@@ -1,16 +1,164 @@
 use crate::contract::Dao;
-use gstd::{msg, prelude::*, ActorId};
+use dao_io::{
+    Member, Proposal, ProposalLane, ProposalMetadata, Role, MAX_TITLE_LENGTH, MAX_URI_LENGTH,
+};
+use ft_io::{FTAction, FTEvent};
+use gstd::{exec, msg, prelude::*, ActorId};
 
 impl Dao {
     // calculates the funds that the member can redeem based on his shares
+    // the tokens reserved for grants are not redeemable
     pub fn redeemable_funds(&self, share: u128) -> u128 {
         if self.total_shares > 0 {
-            (share.saturating_mul(self.balance)) / self.total_shares
+            (share.saturating_mul(self.free_balance())) / self.total_shares
         } else {
             panic!("Zero total shares in DAO!");
         }
     }
 
+    // calculates the native value that the member can redeem based on his shares
+    pub fn redeemable_value(&self, share: u128) -> u128 {
+        if self.total_shares > 0 {
+            (share.saturating_mul(self.value_balance)) / self.total_shares
+        } else {
+            panic!("Zero total shares in DAO!");
+        }
+    }
+
+    // sends the native value held by the DAO program to the account
+    pub fn send_value(account: &ActorId, value: u128) {
+        if value > 0 {
+            msg::send_bytes(*account, [], value).expect("Error in sending the native value");
+        }
+    }
+
+    // issues shares to the applicant who becomes a DAO member if it is not yet
+    pub fn add_member_shares(&mut self, applicant: &ActorId, shares: u128) {
+        if !self.members.contains_key(applicant) {
+            // the new member's address may be used as a delegate key by another member,
+            // that member gets back its own address as the delegate key
+            if let Some(member_id) = self.member_by_delegate_key.remove(applicant) {
+                if let Some(member) = self.members.get_mut(&member_id) {
+                    member.delegate_key = member_id;
+                }
+                self.member_by_delegate_key.insert(member_id, member_id);
+            }
+            self.members.insert(
+                *applicant,
+                Member {
+                    delegate_key: *applicant,
+                    shares: 0,
+                    yes_votes: Vec::new(),
+                    votes: Vec::new(),
+                    token_id: None,
+                    staked: 0,
+                },
+            );
+            self.member_by_delegate_key.insert(*applicant, *applicant);
+        }
+        let member = self.members.get_mut(applicant).expect("Can't be None");
+        member.shares = member.shares.saturating_add(shares);
+        self.total_shares = self.total_shares.saturating_add(shares);
+    }
+
+    // issues new shares and notifies the token tooling
+    pub fn mint_shares(&mut self, applicant: &ActorId, shares: u128) {
+        self.add_member_shares(applicant, shares);
+        Self::emit_share_transfer(&ActorId::zero(), applicant, shares);
+    }
+
+    // the shares are exposed as a fungible token,
+    // so their movements are sent to the zero address as the token transfer events
+    pub fn emit_share_transfer(from: &ActorId, to: &ActorId, amount: u128) {
+        if amount > 0 {
+            msg::send(
+                ActorId::zero(),
+                FTEvent::Transfer {
+                    from: *from,
+                    to: *to,
+                    amount,
+                },
+                0,
+            )
+            .expect("Error in sending `FTEvent::Transfer`");
+        }
+    }
+
+    // answers the balance and total supply queries of the fungible token,
+    // returns false if the payload is not one of them.
+    // these payloads can't be decoded as `DaoAction`:
+    // they are shorter than the DAO actions with the same indices
+    pub fn reply_to_token_query(&self, payload: &[u8]) -> bool {
+        let mut input = payload;
+        let reply = match FTAction::decode(&mut input) {
+            Ok(FTAction::BalanceOf(account)) if input.is_empty() => {
+                FTEvent::Balance(self.members.get(&account).map_or(0, |member| member.shares))
+            }
+            Ok(FTAction::TotalSupply) if input.is_empty() => {
+                FTEvent::TotalSupply(self.total_shares)
+            }
+            _ => return false,
+        };
+        msg::reply(reply, 0).expect("Error in a reply `FTEvent`");
+        true
+    }
+
+    // checks that the proposal the member voted YES on is still to be decided
+    fn is_pending(&self, proposal_id: u128) -> bool {
+        matches!(
+            self.proposals.get(&proposal_id),
+            Some(proposal) if !proposal.processed && !proposal.aborted && !proposal.cancelled
+        )
+    }
+
+    // the shares that the member can ragequit or transfer:
+    // the shares voted YES on the pending proposals are locked regardless of the processing order
+    // and the shares staked on the conviction requests are locked until they are withdrawn
+    pub fn unlocked_shares(&self, member: &Member) -> u128 {
+        let locked = member
+            .yes_votes
+            .iter()
+            .filter(|(proposal_id, _)| self.is_pending(*proposal_id))
+            .map(|(_, shares)| *shares)
+            .max()
+            .unwrap_or_default();
+        member.shares.saturating_sub(locked.max(member.staked))
+    }
+
+    // the shares that the member can transfer:
+    // the shares voted in any way on the pending proposals are locked as well,
+    // otherwise the recipient could vote with them again
+    pub fn transferable_shares(&self, member: &Member) -> u128 {
+        let voted = member
+            .votes
+            .iter()
+            .filter(|(proposal_id, _)| self.is_pending(*proposal_id))
+            .map(|(_, shares)| *shares)
+            .max()
+            .unwrap_or_default();
+        self.unlocked_shares(member)
+            .min(member.shares.saturating_sub(voted))
+    }
+
+    // forgets the votes on the processed and aborted proposals
+    pub fn release_yes_votes(&mut self, member_id: &ActorId) {
+        let Some(member) = self.members.get(member_id) else {
+            return;
+        };
+        let pending = |votes: &Vec<(u128, u128)>| -> Vec<(u128, u128)> {
+            votes
+                .iter()
+                .copied()
+                .filter(|(proposal_id, _)| self.is_pending(*proposal_id))
+                .collect()
+        };
+        let (yes_votes, votes) = (pending(&member.yes_votes), pending(&member.votes));
+        if let Some(member) = self.members.get_mut(member_id) {
+            member.yes_votes = yes_votes;
+            member.votes = votes;
+        }
+    }
+
     // checks that account is DAO member
     pub fn is_member(&self, account: &ActorId) -> bool {
         matches!(self.members.get(account), Some(member) if member.shares != 0)
@@ -25,6 +173,32 @@ impl Dao {
         }
     }
 
+    // computes the starting period for a new proposal in the lane
+    // there should be a minimum time interval between proposals (period_duration) so that members have time to ragequit
+    pub fn next_starting_period(&self, lane: ProposalLane) -> u64 {
+        let starting_period = exec::block_timestamp();
+        let Some(previous_id) = self.lanes.get(&lane) else {
+            return starting_period;
+        };
+        let previous_starting_period = self
+            .proposals
+            .get(previous_id)
+            .expect("Error getting proposal")
+            .starting_period;
+        starting_period.max(previous_starting_period + self.period_duration)
+    }
+
+    // queues the proposal in its lane and returns its ID
+    pub fn insert_proposal(&mut self, mut proposal: Proposal) -> u128 {
+        let lane = proposal.lane();
+        let proposal_id = self.proposal_id;
+        proposal.starting_period = self.next_starting_period(lane);
+        proposal.previous_in_lane = self.lanes.insert(lane, proposal_id);
+        self.proposals.insert(proposal_id, proposal);
+        self.proposal_id = self.proposal_id.saturating_add(1);
+        proposal_id
+    }
+
     // Determine either this is a new transaction
     // or the transaction which has to be completed
     pub fn get_transaction_id(&mut self, transaction_id: Option<u64>) -> u64 {
@@ -39,10 +213,31 @@ impl Dao {
     }
 
     pub fn assert_admin(&self) {
-        assert_eq!(msg::source(), self.admin, "msg::source() must be DAO admin");
+        assert!(
+            self.admins.contains(&msg::source()),
+            "msg::source() must be DAO admin"
+        );
+    }
+
+    pub fn has_role(&self, account: &ActorId, role: Role) -> bool {
+        matches!(self.roles.get(account), Some(roles) if roles.contains(&role))
+    }
+
+    pub fn assert_not_paused(&self) {
+        assert!(!self.paused, "DAO is paused");
     }
 
     pub fn assert_not_zero_address(address: &ActorId) {
         assert!(!address.is_zero(), "Zero address");
     }
+
+    // only the title and the reference to the content are stored on-chain so they are bounded
+    pub fn validate_metadata(metadata: &ProposalMetadata) {
+        if metadata.title.len() > MAX_TITLE_LENGTH {
+            panic!("Proposal title is too long");
+        }
+        if metadata.uri.len() > MAX_URI_LENGTH {
+            panic!("Proposal URI is too long");
+        }
+    }
 }
@@ -0,0 +1,58 @@
+use crate::{contract::Dao, ft_messages::balance_of, nft_messages::owner_of};
+use dao_io::*;
+use gstd::{exec, prelude::*, ActorId};
+
+impl Dao {
+    // checks that account is in the whitelist and its entry has not expired
+    pub fn is_whitelisted(&self, account: &ActorId) -> bool {
+        matches!(
+            self.whitelist.get(account),
+            Some(expires_at) if expires_at.map_or(true, |expires_at| exec::block_timestamp() <= expires_at)
+        )
+    }
+
+    // checks the applicant that isn't in the whitelist by querying the gate program
+    pub async fn passes_membership_gate(
+        &self,
+        applicant: &ActorId,
+        gate_token_id: Option<NftTokenId>,
+    ) -> bool {
+        match self.membership_gate {
+            Some(MembershipGate::Nft(program)) => match gate_token_id {
+                Some(token_id) => owner_of(&program, token_id).await == Some(*applicant),
+                None => false,
+            },
+            Some(MembershipGate::Token {
+                program,
+                min_balance,
+            }) => balance_of(&program, applicant)
+                .await
+                .map_or(false, |balance| balance >= min_balance),
+            None => false,
+        }
+    }
+
+    pub fn assert_whitelist_managed_by_admin(&self) {
+        if self.whitelist_mode == WhitelistMode::Governance {
+            panic!("Whitelist can be changed only by a governance proposal");
+        }
+    }
+
+    pub fn validate_whitelist_update(update: &WhitelistUpdate) {
+        if update.add.is_empty() && update.remove.is_empty() {
+            panic!("Whitelist update is empty");
+        }
+        update.add.iter().for_each(Self::assert_not_zero_address);
+    }
+
+    // adds accounts to the whitelist (or updates the expiry of existing entries)
+    // and then removes accounts from it
+    pub fn apply_whitelist_update(&mut self, update: &WhitelistUpdate) {
+        for account in &update.add {
+            self.whitelist.insert(*account, update.expires_at);
+        }
+        for account in &update.remove {
+            self.whitelist.remove(account);
+        }
+    }
+}
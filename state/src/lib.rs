@@ -12,8 +12,24 @@ pub mod metafns {
         DaoState::is_member(state, &account)
     }
 
-    pub fn is_in_whitelist(state: State, account: ActorId) -> bool {
-        DaoState::is_in_whitelist(state, &account)
+    pub fn is_in_whitelist(state: State, account: ActorId, timestamp: u64) -> bool {
+        DaoState::is_in_whitelist(state, &account, timestamp)
+    }
+
+    pub fn is_admin(state: State, account: ActorId) -> bool {
+        DaoState::is_admin(state, &account)
+    }
+
+    pub fn has_role(state: State, account: ActorId, role: Role) -> bool {
+        DaoState::has_role(state, &account, role)
+    }
+
+    pub fn get_budgets(state: State, account: ActorId) -> Vec<Budget> {
+        DaoState::get_budgets(state, &account)
+    }
+
+    pub fn get_admin_operation(state: State, id: u64) -> PendingAdminOperation {
+        DaoState::get_admin_operation(state, id).expect("Invalid admin operation id")
     }
 
     pub fn get_proposal_id(state: State) -> u128 {
@@ -24,7 +40,44 @@ pub mod metafns {
         DaoState::get_proposal_info(state, id).expect("Invalid proposal id")
     }
 
+    pub fn get_proposal_metadata(state: State, id: u128) -> ProposalMetadata {
+        DaoState::get_proposal_metadata(state, id).expect("Invalid proposal id")
+    }
+
+    pub fn name(_state: State) -> String {
+        String::from(SHARES_NAME)
+    }
+
+    pub fn symbol(_state: State) -> String {
+        String::from(SHARES_SYMBOL)
+    }
+
+    pub fn decimals(_state: State) -> u8 {
+        SHARES_DECIMALS
+    }
+
+    pub fn balance_of(state: State, account: ActorId) -> u128 {
+        DaoState::balance_of(state, &account)
+    }
+
+    pub fn total_supply(state: State) -> u128 {
+        state.total_shares
+    }
+
     pub fn get_member_info(state: State, account: ActorId) -> Member {
         DaoState::get_member_info(state, &account).expect("Invalid member account")
     }
+
+    pub fn get_comments(
+        state: State,
+        proposal_id: u128,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ProposalComment> {
+        DaoState::get_comments(state, proposal_id, offset, limit)
+    }
+
+    pub fn get_grant(state: State, id: u128) -> Grant {
+        DaoState::get_grant(state, id).expect("Invalid grant id")
+    }
 }
@@ -0,0 +1,223 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gstd::{prelude::*, ActorId};
+use gtest::{Program, System};
+
+#[test]
+fn multisig_admin() {
+    let system = System::new();
+    system.init_logger();
+    let _ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let signers = [ADMIN, 101, 102];
+    let applicant: u64 = 200;
+    let user: u64 = 1000;
+
+    // must fail since the threshold exceeds the number of signers
+    dao.set_admin_signers(ADMIN, &signers, 4, true);
+
+    // must fail since the account is not an admin signer
+    dao.set_admin_signers(user, &signers, 2, true);
+
+    dao.set_admin_signers(ADMIN, &signers, 2, false);
+
+    // the whitelisting now needs the confirmation of the second signer
+    dao.propose_admin_operation(ADMIN, DaoAction::AddToWhiteList(applicant.into()), 1, false);
+
+    // must fail since the account is not an admin signer
+    dao.confirm_admin_operation(
+        user,
+        1,
+        DaoEvent::MemberAddedToWhitelist(applicant.into()),
+        true,
+    );
+
+    // must fail since the signer has already confirmed the operation
+    dao.confirm_admin_operation(
+        ADMIN,
+        1,
+        DaoEvent::MemberAddedToWhitelist(applicant.into()),
+        true,
+    );
+
+    dao.confirm_admin_operation(
+        101,
+        1,
+        DaoEvent::MemberAddedToWhitelist(applicant.into()),
+        false,
+    );
+
+    // must fail since the operation has already been executed
+    dao.confirm_admin_operation(
+        102,
+        1,
+        DaoEvent::MemberAddedToWhitelist(applicant.into()),
+        true,
+    );
+
+    dao.propose_admin_operation(101, DaoAction::Pause, 2, false);
+    system.spend_blocks((ADMIN_OPERATION_LIFETIME / 1000) as u32 + 1);
+
+    // must fail since the operation has expired
+    dao.confirm_admin_operation(ADMIN, 2, DaoEvent::Paused, true);
+
+    dao.propose_admin_operation(101, DaoAction::Pause, 3, false);
+    dao.confirm_admin_operation(102, 3, DaoEvent::Paused, false);
+
+    // must fail since the DAO is paused
+    dao.submit_funding_proposal(ADMIN, 0, applicant, 10_000, 0, true);
+
+    dao.propose_admin_operation(ADMIN, DaoAction::Unpause, 4, false);
+    dao.confirm_admin_operation(101, 4, DaoEvent::Unpaused, false);
+
+    dao.submit_funding_proposal(ADMIN, 0, applicant, 10_000, 0, false);
+
+    // must fail since the account is already an admin signer
+    dao.propose_admin_operation(101, DaoAction::ProposeAdmin(102.into()), 5, true);
+
+    // the new admin takes the place of the proposing signer only
+    dao.propose_admin_operation(101, DaoAction::ProposeAdmin(user.into()), 5, false);
+    dao.confirm_admin_operation(102, 5, DaoEvent::AdminTransferProposed(user.into()), false);
+    dao.accept_admin(user, false);
+    let state: DaoState = dao.read_state().expect("Unable to read the state");
+    assert_eq!(state.admins, [ADMIN, user, 102].map(ActorId::from).to_vec());
+    assert_eq!(state.admin_threshold, 2);
+}
+
+#[test]
+fn roles() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let whitelister: u64 = 101;
+    let guardian: u64 = 102;
+    let applicant: u64 = 200;
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+
+    // must fail since the account is not an admin signer
+    dao.grant_role(whitelister, whitelister, Role::Whitelister, true);
+
+    dao.grant_role(ADMIN, whitelister, Role::Whitelister, false);
+    dao.grant_role(ADMIN, guardian, Role::Guardian, false);
+
+    // the whitelister adds the applicant without the admin
+    dao.add_to_whitelist(whitelister, applicant, false);
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    dao.submit_vote(ADMIN, 0, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+
+    // must fail since the whitelister is neither a member nor a proposal processor
+    let res = dao.send(whitelister, DaoAction::ProcessProposal(0));
+    assert!(res.main_failed());
+
+    // must fail since the account is not a guardian
+    dao.pause(whitelister, true);
+
+    dao.pause(guardian, false);
+
+    // must fail since the DAO is paused
+    dao.process_proposal(0, true, true);
+}
+
+#[test]
+fn treasurer_spending_limit() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let treasurer: u64 = 400;
+    let receiver: u64 = 300;
+    let limit: u128 = 1_000;
+    let member = APPLICANTS[0];
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+    dao.grant_role(ADMIN, treasurer, Role::Treasurer, false);
+
+    assert!(!dao
+        .send(
+            ADMIN,
+            DaoAction::SubmitGovernanceProposal {
+                action: GovernanceAction::SetTreasurerSpendingLimit(limit),
+                quorum: 0,
+                metadata: ProposalMetadata::default(),
+            },
+        )
+        .main_failed());
+    dao.submit_vote(member, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+
+    dao.treasurer_payout(treasurer, receiver, 600, false);
+    // must fail since the limit is shared by all payouts of the period
+    dao.treasurer_payout(treasurer, receiver, 500, true);
+    dao.treasurer_payout(treasurer, receiver, 400, false);
+    // must fail since the limit for the period is spent
+    dao.treasurer_payout(treasurer, receiver, 1, true);
+
+    // the limit is renewed in the next period
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.treasurer_payout(treasurer, receiver, 500, false);
+    ftoken.check_balance(receiver, limit + 500);
+
+    dao.pause(ADMIN, false);
+
+    // must fail since the DAO is paused
+    dao.treasurer_payout(treasurer, receiver, 1, true);
+}
+
+#[test]
+fn admin_transfer() {
+    let system = System::new();
+    system.init_logger();
+    let _ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let new_admin: u64 = 101;
+    let user: u64 = 1000;
+
+    // must fail since the admin transfer has not been proposed
+    dao.accept_admin(new_admin, true);
+    dao.cancel_admin_transfer(ADMIN, new_admin, true);
+
+    // must fail since the account is not an admin
+    dao.propose_admin(user, new_admin, true);
+
+    dao.propose_admin(ADMIN, user, false);
+    dao.cancel_admin_transfer(ADMIN, user, false);
+
+    // must fail since the admin transfer has been cancelled
+    dao.accept_admin(user, true);
+
+    dao.propose_admin(ADMIN, new_admin, false);
+
+    // must fail since the account is not the proposed admin
+    dao.accept_admin(user, true);
+
+    // the admin keeps the role until the new admin accepts it
+    dao.add_to_whitelist(ADMIN, user, false);
+
+    dao.accept_admin(new_admin, false);
+
+    // must fail since the account is no longer an admin
+    dao.add_to_whitelist(ADMIN, 200, true);
+
+    dao.add_to_whitelist(new_admin, 200, false);
+}
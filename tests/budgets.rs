@@ -0,0 +1,107 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gtest::{Program, System};
+
+const OTHER_TOKEN: u64 = 3;
+
+#[test]
+fn treasurer_budget() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let treasurer: u64 = 400;
+    let receiver: u64 = 300;
+    let quorum: u128 = 50;
+    let budget: u128 = 1_000;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    dao.grant_role(ADMIN, treasurer, Role::Treasurer, false);
+
+    // must fail since the treasurer has no budget
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 100, 0, 0, true);
+
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::SetBudget {
+            account: treasurer.into(),
+            token: TOKEN_ID.into(),
+            amount: budget,
+            periods_per_epoch: 1,
+        },
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+
+    let epoch = system.block_timestamp() / PERIOD_DURATION;
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 600, epoch, 600, false);
+
+    // must fail since the amount exceeds the budget left for the epoch
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 500, epoch, 1_100, true);
+
+    // must fail since the account is not a treasurer
+    dao.spend_budget(APPLICANTS[0], TOKEN_ID, receiver, 100, epoch, 100, true);
+
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 400, epoch, budget, false);
+    ftoken.check_balance(receiver, budget);
+
+    // the budget is renewed in the next epoch
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 500, epoch + 1, 500, false);
+    ftoken.check_balance(receiver, budget + 500);
+    ftoken.check_balance(DAO_ID, 10 * token_tribute - budget - 500);
+
+    dao.pause(ADMIN, false);
+
+    // must fail since the DAO is paused
+    dao.spend_budget(treasurer, TOKEN_ID, receiver, 100, epoch + 1, 600, true);
+
+    assert!(!dao.send(ADMIN, DaoAction::Unpause).main_failed());
+
+    proposal_id += 1;
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::SetBudget {
+            account: treasurer.into(),
+            token: OTHER_TOKEN.into(),
+            amount: budget,
+            periods_per_epoch: 1,
+        },
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+
+    // must fail since the DAO holds none of the token outside the escrowed tributes
+    let epoch = system.block_timestamp() / PERIOD_DURATION;
+    dao.spend_budget(treasurer, OTHER_TOKEN, receiver, 100, epoch, 100, true);
+}
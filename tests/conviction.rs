@@ -0,0 +1,106 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gstd::prelude::*;
+use gtest::{Program, System};
+
+#[test]
+fn conviction_voting() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let member = APPLICANTS[0];
+    let receiver: u64 = 300;
+    let amount: u128 = 1_000;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    // must fail since conviction voting is disabled
+    dao.submit_conviction_request(member, 0, receiver, amount, true);
+
+    // must fail since the threshold weight is zero
+    dao.submit_governance_proposal(
+        ADMIN,
+        1,
+        GovernanceAction::SetConvictionConfig(Some(ConvictionConfig {
+            decay: CONVICTION_BASE / 2,
+            max_ratio: CONVICTION_BASE / 2,
+            weight: 0,
+        })),
+        50,
+        true,
+    );
+
+    dao.submit_governance_proposal(
+        ADMIN,
+        1,
+        GovernanceAction::SetConvictionConfig(Some(ConvictionConfig {
+            decay: CONVICTION_BASE / 2,
+            max_ratio: CONVICTION_BASE / 2,
+            weight: CONVICTION_BASE / 100,
+        })),
+        50,
+        false,
+    );
+    dao.submit_vote(member, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+
+    dao.submit_conviction_request(member, 0, receiver, amount, false);
+
+    // must fail since the member doesn't have enough shares
+    dao.stake_conviction(member, 0, shares_requested + 1, true);
+    dao.stake_conviction(member, 0, shares_requested, false);
+
+    // must fail since the staked shares are locked
+    dao.ragequit(member, shares_requested, 0, true);
+    // must fail since the conviction hasn't accumulated yet
+    dao.execute_conviction_request(member, 0, receiver, amount, true);
+
+    // the member can withdraw the stake and stake it again
+    dao.withdraw_conviction(member, 0, shares_requested, false);
+    // must fail since the member has no stake left
+    dao.withdraw_conviction(member, 0, 1, true);
+
+    // the delegate key stakes the shares of the member
+    let delegate: u64 = 301;
+    dao.update_delegate_key(member, delegate, false);
+    // must fail since the member's address is no longer its delegate key
+    dao.stake_conviction(member, 0, shares_requested, true);
+    let res = dao.send(
+        delegate,
+        DaoAction::StakeConviction {
+            request_id: 0,
+            amount: shares_requested,
+        },
+    );
+    let reply = DaoEvent::ConvictionStaked {
+        member: member.into(),
+        request_id: 0,
+        amount: shares_requested,
+    }
+    .encode();
+    assert!(res.contains(&(delegate, reply)));
+
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    // must fail since the DAO is paused
+    dao.pause(ADMIN, false);
+    dao.execute_conviction_request(member, 0, receiver, amount, true);
+    assert!(!dao.send(ADMIN, DaoAction::Unpause).main_failed());
+
+    dao.execute_conviction_request(member, 0, receiver, amount, false);
+    ftoken.check_balance(receiver, amount);
+
+    // must fail since the request has already been executed
+    dao.execute_conviction_request(member, 0, receiver, amount, true);
+
+    // the staked shares are released on execution
+    let funds = ((token_tribute - amount) * shares_requested) / (shares_requested + 1);
+    dao.ragequit(member, shares_requested, funds, false);
+}
@@ -328,3 +328,79 @@ fn ragequit() {
     ftoken.check_balance(18, funds);
     ftoken.check_balance(DAO_ID, balance + token_tribute);
 }
+
+#[test]
+fn ragequit_unlocked_shares() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let member = APPLICANTS[0];
+    let applicant: u64 = 200;
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let extra_shares: u128 = 5_000;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    // the member requests more shares
+    dao.submit_membership_proposal(ADMIN, 1, member, 0, extra_shares, 0, false);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(ADMIN, 2, applicant, 0, shares_requested, 0, false);
+    dao.submit_vote(ADMIN, 1, Vote::Yes, false);
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32 + 1);
+    dao.submit_vote(member, 2, Vote::Yes, false);
+
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+
+    // must fail since the shares voted YES on the pending proposal are locked
+    dao.ragequit(member, extra_shares + 1, 0, true);
+
+    // the shares received after the vote can be withdrawn
+    let total_shares = shares_requested + extra_shares + 1;
+    let funds = (token_tribute * extra_shares) / total_shares;
+    dao.ragequit(member, extra_shares, funds, false);
+    ftoken.check_balance(member, funds);
+}
+
+#[test]
+fn ragequit_after_abort() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let member = APPLICANTS[0];
+    let applicant: u64 = 200;
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    ftoken.mint(2, applicant, applicant, token_tribute);
+    ftoken.approve(3, applicant, DAO_ID, token_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(
+        ADMIN,
+        1,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    dao.submit_vote(member, 1, Vote::Yes, false);
+
+    // must fail since the member voted YES and the proposal is pending
+    dao.ragequit(member, shares_requested, 0, true);
+
+    // the aborted proposal no longer locks the shares
+    dao.abort(applicant, 1, false);
+    let funds = (token_tribute * shares_requested) / (shares_requested + 1);
+    dao.ragequit(member, shares_requested, funds, false);
+    ftoken.check_balance(member, funds);
+}
@@ -0,0 +1,481 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gstd::prelude::*;
+use gtest::{Program, System};
+
+// the time it takes to vote on and process a proposal
+const PROPOSAL_DURATION: u64 = (VOTING_PERIOD_LENGTH + 1) * 1000;
+
+#[test]
+fn vesting_grant() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let amount: u128 = 40_000;
+    let receiver: u64 = 300;
+    let quorum: u128 = 50;
+    let mut total_shares = 10 * shares_requested + 1;
+    let mut balance = 10 * token_tribute;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    let schedule = VestingSchedule {
+        start: system.block_timestamp(),
+        cliff: 2 * PROPOSAL_DURATION,
+        duration: 4 * PROPOSAL_DURATION,
+    };
+
+    // must fail since the amount is zero
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        0,
+        GrantSchedule::Vesting(schedule),
+        quorum,
+        true,
+    );
+
+    // must fail since the cliff exceeds the duration
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        amount,
+        GrantSchedule::Vesting(VestingSchedule {
+            cliff: 5 * PROPOSAL_DURATION,
+            ..schedule
+        }),
+        quorum,
+        true,
+    );
+
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        amount,
+        GrantSchedule::Vesting(schedule),
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    let grant_id = proposal_id;
+
+    // the grant is reserved on the DAO balance
+    ftoken.check_balance(receiver, 0);
+    ftoken.check_balance(DAO_ID, balance);
+
+    // must fail since the cliff has not passed
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+
+    // must fail since the account is not the recipient
+    dao.claim_grant(APPLICANTS[0], grant_id, receiver, 0, true);
+
+    // the funding can't be paid out of the tokens reserved for the grant
+    proposal_id += 1;
+    dao.submit_funding_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        balance - amount + 1,
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, false, false);
+    ftoken.check_balance(receiver, 0);
+
+    // the reserved tokens are not redeemable
+    let funds = ((balance - amount) * shares_requested) / total_shares;
+    dao.ragequit(APPLICANTS[0], shares_requested, funds, false);
+    total_shares -= shares_requested;
+    balance -= funds;
+
+    system.spend_blocks(3 * (VOTING_PERIOD_LENGTH as u32 + 1));
+
+    // the whole grant has been released
+    dao.claim_grant(receiver, grant_id, receiver, amount, false);
+    balance -= amount;
+    ftoken.check_balance(receiver, amount);
+    ftoken.check_balance(DAO_ID, balance);
+
+    // must fail since the grant has been claimed
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+
+    // the grant can't exceed the free DAO balance
+    proposal_id += 1;
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        balance + 1,
+        GrantSchedule::Vesting(schedule),
+        quorum,
+        false,
+    );
+    for applicant in &APPLICANTS[1..] {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, false, false);
+
+    // the grant that is cancelled before the cliff
+    proposal_id += 1;
+    let schedule = VestingSchedule {
+        start: system.block_timestamp(),
+        cliff: 4 * PROPOSAL_DURATION,
+        duration: 4 * PROPOSAL_DURATION,
+    };
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        amount,
+        GrantSchedule::Vesting(schedule),
+        quorum,
+        false,
+    );
+    for applicant in &APPLICANTS[1..] {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    let grant_id = proposal_id;
+
+    proposal_id += 1;
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::CancelGrant(grant_id),
+        quorum,
+        false,
+    );
+    for applicant in &APPLICANTS[1..] {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+
+    // must fail since the grant has been cancelled
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id + 1,
+        GovernanceAction::CancelGrant(grant_id),
+        quorum,
+        true,
+    );
+
+    system.spend_blocks(3 * (VOTING_PERIOD_LENGTH as u32 + 1));
+
+    // must fail since nothing had been released before the grant was cancelled
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+
+    // the cancelled grant is redeemable again
+    let funds = (balance * shares_requested) / total_shares;
+    dao.ragequit(APPLICANTS[1], shares_requested, funds, false);
+    ftoken.check_balance(APPLICANTS[1], funds);
+}
+
+#[test]
+fn milestone_grant() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let receiver: u64 = 300;
+    let quorum: u128 = 50;
+    let balance = 10 * token_tribute;
+    let total_shares = 10 * shares_requested + 1;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    let milestones: Vec<Milestone> = [10_000, 20_000, 30_000]
+        .into_iter()
+        .map(|amount| Milestone {
+            amount,
+            description: String::from(""),
+        })
+        .collect();
+
+    // must fail since the milestones don't add up to the amount
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        50_000,
+        GrantSchedule::Milestones(milestones.clone()),
+        quorum,
+        true,
+    );
+
+    // must fail since the milestone description is limited
+    let mut long_description = milestones.clone();
+    long_description[0].description = "d".repeat(MAX_TITLE_LENGTH + 1);
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        60_000,
+        GrantSchedule::Milestones(long_description),
+        quorum,
+        true,
+    );
+
+    // must fail since the number of milestones is limited
+    let too_many_milestones = vec![
+        Milestone {
+            amount: 1,
+            description: String::from(""),
+        };
+        MAX_MILESTONES + 1
+    ];
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        MAX_MILESTONES as u128 + 1,
+        GrantSchedule::Milestones(too_many_milestones),
+        quorum,
+        true,
+    );
+
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        60_000,
+        GrantSchedule::Milestones(milestones),
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    let grant_id = proposal_id;
+
+    // must fail since no milestone has been released
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+
+    // must fail since the milestones are released in order
+    dao.release_milestone(ADMIN, grant_id, 1, 0, true);
+
+    // must fail since the account is not an admin signer
+    dao.release_milestone(APPLICANTS[0], grant_id, 0, 0, true);
+
+    // the first milestone is approved by the admin signers
+    dao.release_milestone(ADMIN, grant_id, 0, 10_000, false);
+    dao.claim_grant(receiver, grant_id, receiver, 10_000, false);
+    ftoken.check_balance(receiver, 10_000);
+
+    // the second milestone is released by a vote
+    proposal_id += 1;
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::ReleaseMilestone {
+            grant_id,
+            milestone: 1,
+        },
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    dao.claim_grant(receiver, grant_id, receiver, 20_000, false);
+    ftoken.check_balance(receiver, 30_000);
+
+    // the last milestone is cancelled and returns to the free balance
+    proposal_id += 1;
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::CancelGrant(grant_id),
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+
+    // must fail since the grant has been cancelled
+    dao.release_milestone(ADMIN, grant_id, 2, 0, true);
+
+    let funds = ((balance - 30_000) * shares_requested) / total_shares;
+    dao.ragequit(APPLICANTS[0], shares_requested, funds, false);
+}
+
+#[test]
+fn recurring_grant() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let receiver: u64 = 300;
+    let quorum: u128 = 50;
+    let payment: u128 = 5_000;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    // the first payment is due when the proposal is processed
+    let schedule = RecurringSchedule {
+        start: system.block_timestamp() + PROPOSAL_DURATION,
+        interval: PROPOSAL_DURATION,
+        payment,
+        until: RecurringLimit::Payments(4),
+    };
+
+    // must fail since the payments don't add up to the amount
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        3 * payment,
+        GrantSchedule::Recurring(schedule),
+        quorum,
+        true,
+    );
+
+    // must fail since the end date is before the start
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        4 * payment,
+        GrantSchedule::Recurring(RecurringSchedule {
+            until: RecurringLimit::EndDate(system.block_timestamp()),
+            ..schedule
+        }),
+        quorum,
+        true,
+    );
+
+    // must fail since the number of payments until the end date doesn't fit into `u32`
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        4 * payment,
+        GrantSchedule::Recurring(RecurringSchedule {
+            interval: 1,
+            until: RecurringLimit::EndDate(schedule.start + (1 << 32) + 3),
+            ..schedule
+        }),
+        quorum,
+        true,
+    );
+
+    dao.submit_grant_proposal(
+        ADMIN,
+        proposal_id,
+        receiver,
+        4 * payment,
+        GrantSchedule::Recurring(schedule),
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    let grant_id = proposal_id;
+
+    // anyone can trigger the due payment
+    dao.claim_grant(APPLICANTS[0], grant_id, receiver, payment, false);
+    ftoken.check_balance(receiver, payment);
+
+    // must fail since the next payment is not due yet
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+
+    // the payments are stopped by a proposal, the payments due by then can be claimed
+    proposal_id += 1;
+    dao.submit_governance_proposal(
+        ADMIN,
+        proposal_id,
+        GovernanceAction::CancelGrant(grant_id),
+        quorum,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+
+    dao.pause(ADMIN, false);
+
+    // must fail since the DAO is paused
+    dao.claim_grant(receiver, grant_id, receiver, payment, true);
+
+    assert!(!dao.send(ADMIN, DaoAction::Unpause).main_failed());
+    dao.claim_grant(receiver, grant_id, receiver, payment, false);
+    ftoken.check_balance(receiver, 2 * payment);
+
+    // must fail since the grant has been cancelled
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.claim_grant(receiver, grant_id, receiver, 0, true);
+    ftoken.check_balance(DAO_ID, 10 * token_tribute - 2 * payment);
+}
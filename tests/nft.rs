@@ -0,0 +1,85 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gstd::prelude::*;
+use gtest::{Program, System};
+
+const NFT_ID: u64 = 3;
+
+#[test]
+fn membership_tokens() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao_with_membership_nft(&system, NFT_ID);
+    let nft = Program::nft(&system, DAO_ID);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let buyer: u64 = 300;
+
+    // the tokens are minted to the members on joining
+    for (proposal_id, applicant) in APPLICANTS[..2].iter().enumerate() {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id as u128,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+    }
+    nft.check_owner(0, APPLICANTS[0]);
+    nft.check_owner(1, APPLICANTS[1]);
+
+    dao.submit_governance_proposal(
+        ADMIN,
+        2,
+        GovernanceAction::SetShareTransferPolicy(ShareTransferPolicy::Whitelisted),
+        0,
+        false,
+    );
+    dao.submit_vote(ADMIN, 2, Vote::Yes, false);
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(2, true, false);
+
+    // the transfer fails until the member approves the DAO to take the token back
+    dao.add_to_whitelist(ADMIN, buyer, false);
+    let res = dao.send(
+        APPLICANTS[0],
+        DaoAction::TransferShares {
+            to: buyer.into(),
+            amount: shares_requested,
+        },
+    );
+    let transaction_id = res
+        .log()
+        .iter()
+        .find_map(|log| match DaoEvent::decode(&mut log.payload()) {
+            Ok(DaoEvent::TransactionFailed(transaction_id)) => Some(transaction_id),
+            _ => None,
+        })
+        .expect("The transfer must fail without the approval");
+    nft.check_owner(0, APPLICANTS[0]);
+
+    // the token of the member transferring all the shares is burned
+    // and the buyer receives a new one
+    nft.approve(0, APPLICANTS[0], DAO_ID, 0);
+    let res = dao.send(APPLICANTS[0], DaoAction::Continue(transaction_id));
+    let reply = DaoEvent::SharesTransferred {
+        from: APPLICANTS[0].into(),
+        to: buyer.into(),
+        amount: shares_requested,
+    }
+    .encode();
+    assert!(res.contains(&(APPLICANTS[0], reply)));
+    nft.check_owner(0, 0);
+    nft.check_owner(2, buyer);
+
+    // the token is burned on full ragequit
+    nft.approve(0, APPLICANTS[1], DAO_ID, 1);
+    let funds = (2 * token_tribute * shares_requested) / (2 * shares_requested + 1);
+    dao.ragequit(APPLICANTS[1], shares_requested, funds, false);
+    nft.check_owner(1, 0);
+}
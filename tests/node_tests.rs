@@ -16,6 +16,7 @@ async fn dilution_bound_gclient() -> gclient::Result<()> {
         10000000,
         3,
         10000000,
+        10000000,
     )
     .await?;
 
@@ -2,6 +2,7 @@ pub mod utils;
 
 use crate::utils::*;
 use dao_io::*;
+use gstd::prelude::*;
 use gtest::{Program, System};
 
 #[test]
@@ -156,3 +157,606 @@ fn funding_proposals() {
     // check balance of DAO
     ftoken.check_balance(DAO_ID, 10 * token_tribute - amount);
 }
+
+#[test]
+fn proposal_lanes() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+
+    ftoken.mint(0, applicant, applicant, 2 * token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, 2 * token_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    dao.submit_governance_proposal(
+        ADMIN,
+        1,
+        GovernanceAction::SetTreasurerSpendingLimit(token_tribute),
+        0,
+        false,
+    );
+    dao.submit_membership_proposal(
+        ADMIN,
+        2,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+
+    // the governance proposal starts right away in its own lane
+    dao.submit_vote(ADMIN, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    // must fail since the previous proposal in the lane must be processed
+    dao.process_proposal(2, false, true);
+
+    dao.process_proposal(0, false, false);
+    dao.process_proposal(2, false, false);
+}
+
+#[test]
+fn cancel_proposal() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, 2 * token_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    ftoken.check_balance(applicant, 0);
+
+    // must fail since only the proposer can cancel the proposal
+    dao.cancel_proposal(applicant, 0, true);
+
+    dao.cancel_proposal(ADMIN, 0, false);
+    ftoken.check_balance(applicant, token_tribute);
+
+    // must fail since the proposal has already been cancelled
+    dao.cancel_proposal(ADMIN, 0, true);
+    // must fail since the proposal has been cancelled
+    dao.submit_vote(ADMIN, 0, Vote::Yes, true);
+
+    dao.submit_membership_proposal(
+        ADMIN,
+        1,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.submit_vote(ADMIN, 1, Vote::Yes, false);
+
+    // must fail since the proposal has already been voted on
+    dao.cancel_proposal(ADMIN, 1, true);
+
+    // the cancelled proposal is skipped in the lane
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32 + 1);
+    dao.process_proposal(0, false, true);
+    dao.process_proposal(1, true, false);
+}
+
+#[test]
+fn early_finalization() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+
+    // must fail since the outcome of the proposal is not decided yet
+    dao.close_voting(ADMIN, 0, true);
+
+    dao.submit_vote(ADMIN, 0, Vote::Yes, false);
+    // must fail since the abort window isn't over
+    dao.close_voting(ADMIN, 0, true);
+
+    system.spend_blocks((ABORT_WINDOW / 1000) as u32 + 1);
+    // must fail since only members, delegates or proposal processors can close the voting
+    dao.close_voting(applicant, 0, true);
+    dao.close_voting(ADMIN, 0, false);
+    let state: DaoState = dao.read_state().expect("Unable to read the state");
+    assert!(DaoState::get_proposal_info(state, 0)
+        .voting_closed_at
+        .is_some());
+
+    // must fail since the voting has already been closed
+    dao.close_voting(ADMIN, 0, true);
+
+    // the proposal is processed after the grace period
+    system.spend_blocks((GRACE_PERIOD_LENGTH / 1000) as u32 + 1);
+    dao.process_proposal(0, true, false);
+
+    // must fail since the proposal has already been processed
+    dao.abort(applicant, 0, true);
+}
+
+#[test]
+fn optimistic_proposals() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let amount_cap: u128 = 1_000;
+    let member = APPLICANTS[0];
+    let receiver: u64 = 300;
+    let optimistic_funding = |amount: u128| DaoAction::SubmitFundingProposal {
+        applicant: receiver.into(),
+        amount,
+        value_amount: 0,
+        quorum: 50,
+        metadata: ProposalMetadata::default(),
+        optimistic: true,
+    };
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    // must fail since optimistic proposals are disabled
+    assert!(dao
+        .send(ADMIN, optimistic_funding(amount_cap))
+        .main_failed());
+
+    dao.submit_governance_proposal(
+        ADMIN,
+        1,
+        GovernanceAction::SetOptimisticConfig(Some(OptimisticConfig {
+            amount_cap,
+            value_amount_cap: 0,
+            objection_threshold: 10,
+            quorum: 20,
+        })),
+        50,
+        false,
+    );
+    dao.submit_vote(member, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+
+    // must fail since the amount exceeds the cap
+    assert!(dao
+        .send(ADMIN, optimistic_funding(amount_cap + 1))
+        .main_failed());
+
+    // the proposal is rejected if the shares voted either way don't meet the quorum
+    assert!(!dao
+        .send(ADMIN, optimistic_funding(amount_cap))
+        .main_failed());
+    // must fail since the optimistic proposal can't be closed early
+    dao.close_voting(ADMIN, 2, true);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(2, false, false);
+    ftoken.check_balance(receiver, 0);
+
+    // the proposal without objections passes
+    assert!(!dao
+        .send(ADMIN, optimistic_funding(amount_cap))
+        .main_failed());
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.submit_vote(member, 3, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(3, true, false);
+    ftoken.check_balance(receiver, amount_cap);
+
+    // the proposal is rejected if the NO votes exceed the objection threshold
+    assert!(!dao
+        .send(ADMIN, optimistic_funding(amount_cap))
+        .main_failed());
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.submit_vote(member, 4, Vote::No, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(4, false, false);
+    ftoken.check_balance(receiver, amount_cap);
+
+    // the proposal without any votes passes under the lazy consensus
+    dao.submit_governance_proposal(
+        ADMIN,
+        5,
+        GovernanceAction::SetOptimisticConfig(Some(OptimisticConfig {
+            amount_cap,
+            value_amount_cap: 0,
+            objection_threshold: 10,
+            quorum: 0,
+        })),
+        50,
+        false,
+    );
+    dao.submit_vote(member, 5, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(5, true, false);
+
+    assert!(!dao
+        .send(ADMIN, optimistic_funding(amount_cap))
+        .main_failed());
+    system.spend_blocks(
+        ((PERIOD_DURATION + VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32,
+    );
+    dao.process_proposal(6, true, false);
+    ftoken.check_balance(receiver, 2 * amount_cap);
+}
+
+#[test]
+fn ranked_choice_proposals() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let (first, second, third): (u64, u64, u64) = (200, 201, 202);
+    let (candidate, runner_up): (u64, u64) = (300, 301);
+    let whitelist = |account: u64| ProposalOption {
+        description: String::from(""),
+        action: Some(GovernanceAction::UpdateWhitelist(WhitelistUpdate {
+            add: vec![account.into()],
+            expires_at: None,
+            remove: vec![],
+        })),
+    };
+    let options = vec![
+        whitelist(runner_up),
+        whitelist(candidate),
+        ProposalOption {
+            description: String::from(""),
+            action: None,
+        },
+    ];
+
+    for (proposal_id, (applicant, shares)) in [(first, 4), (second, 3), (third, 3)]
+        .into_iter()
+        .enumerate()
+    {
+        ftoken.mint(0, applicant, applicant, token_tribute);
+        ftoken.approve(1, applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id as u128,
+            applicant,
+            token_tribute,
+            shares,
+        );
+    }
+
+    // must fail since the ranked-choice proposal needs at least two options
+    dao.submit_ranked_choice_proposal(ADMIN, 3, options[..1].to_vec(), 50, true);
+    // must fail since the option description is limited
+    let mut long_description = options.clone();
+    long_description[2].description = "d".repeat(MAX_TITLE_LENGTH + 1);
+    dao.submit_ranked_choice_proposal(ADMIN, 3, long_description, 50, true);
+    dao.submit_ranked_choice_proposal(ADMIN, 3, options, 50, false);
+
+    // must fail since the ranking must be non-empty and list the existing options once
+    dao.submit_ranked_vote(first, 3, vec![], true);
+    dao.submit_ranked_vote(first, 3, vec![3], true);
+    dao.submit_ranked_vote(first, 3, vec![0, 0], true);
+    // must fail since the ranked-choice proposal takes only ranked votes
+    dao.submit_vote(first, 3, Vote::Yes, true);
+
+    dao.submit_ranked_vote(first, 3, vec![0, 2], false);
+    dao.submit_ranked_vote(second, 3, vec![1], false);
+    dao.submit_ranked_vote(third, 3, vec![2, 1], false);
+    dao.submit_ranked_vote(ADMIN, 3, vec![1], false);
+    // must fail since the member has already voted
+    dao.submit_ranked_vote(first, 3, vec![1], true);
+    // must fail since the ranked-choice voting can't be closed early
+    dao.close_voting(ADMIN, 3, true);
+
+    // the third option is eliminated and its ballot moves the second option to the majority
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(3, true, false);
+
+    dao.submit_membership_proposal(ADMIN, 4, candidate, 0, 1, 0, false);
+    // must fail since the option whitelisting the runner-up has lost
+    dao.submit_membership_proposal(ADMIN, 5, runner_up, 0, 1, 0, true);
+}
+
+#[test]
+fn funding_governance_action() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let amount: u128 = 5_000;
+    let member = APPLICANTS[0];
+    let receiver: u64 = 300;
+    let fund = |amount: u128| GovernanceAction::Fund {
+        applicant: receiver.into(),
+        amount,
+    };
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    // must fail since the funding amount is zero
+    dao.submit_governance_proposal(ADMIN, 1, fund(0), 50, true);
+
+    // the proposal fails since the DAO balance doesn't cover the funding
+    dao.submit_governance_proposal(ADMIN, 1, fund(token_tribute + 1), 50, false);
+    dao.submit_vote(member, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, false, false);
+    ftoken.check_balance(receiver, 0);
+
+    // the winning option of the ranked-choice proposal funds the applicant
+    let options = vec![
+        ProposalOption {
+            description: String::from(""),
+            action: Some(fund(amount)),
+        },
+        ProposalOption {
+            description: String::from(""),
+            action: None,
+        },
+    ];
+    dao.submit_ranked_choice_proposal(ADMIN, 2, options, 50, false);
+    dao.submit_ranked_vote(member, 2, vec![0], false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(2, true, false);
+    ftoken.check_balance(receiver, amount);
+
+    // the ragequit payout comes from the tokens left after the funding
+    let funds = (token_tribute - amount) * shares_requested / (shares_requested + 1);
+    dao.ragequit(member, shares_requested, funds, false);
+}
+
+#[test]
+fn signaling_proposals() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let member = APPLICANTS[0];
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    // must fail since only members can submit proposals
+    dao.submit_signaling_proposal(APPLICANTS[1], 1, 50, true);
+    dao.submit_signaling_proposal(member, 1, 50, false);
+    dao.submit_vote(member, 1, Vote::Yes, false);
+
+    // the shares voted YES on the signaling proposal aren't locked
+    let funds = (token_tribute * shares_requested / 2) / (shares_requested + 1);
+    dao.ragequit(member, shares_requested / 2, funds, false);
+    ftoken.check_balance(member, funds);
+
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+}
+
+#[test]
+fn proposal_metadata() {
+    let system = System::new();
+    system.init_logger();
+    let dao = Program::dao(&system);
+    let signaling = |title: usize, uri: usize| DaoAction::SubmitSignalingProposal {
+        quorum: 50,
+        metadata: ProposalMetadata {
+            title: "t".repeat(title),
+            content_hash: [1; 32],
+            uri: "u".repeat(uri),
+        },
+    };
+
+    // must fail since the title and the URI are limited
+    assert!(dao
+        .send(ADMIN, signaling(MAX_TITLE_LENGTH + 1, 0))
+        .main_failed());
+    assert!(dao
+        .send(ADMIN, signaling(0, MAX_URI_LENGTH + 1))
+        .main_failed());
+
+    let res = dao.send(ADMIN, signaling(MAX_TITLE_LENGTH, MAX_URI_LENGTH));
+    assert!(res.contains(&(
+        ADMIN,
+        DaoEvent::SubmitSignalingProposal {
+            proposer: ADMIN.into(),
+            proposal_id: 0,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn proposal_comments() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let member = APPLICANTS[0];
+    let delegate: u64 = 300;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+    dao.update_delegate_key(member, delegate, false);
+
+    dao.submit_signaling_proposal(ADMIN, 1, 50, false);
+    // must fail since only members or delegates can comment
+    dao.comment(APPLICANTS[1], 1, 0, true);
+    dao.comment(delegate, 1, 0, false);
+    dao.comment(ADMIN, 1, 1, false);
+
+    // must fail since the member has commented less than a period ago through the delegate
+    dao.comment(delegate, 1, 2, true);
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32);
+    dao.comment(delegate, 1, 2, false);
+
+    // must fail since the voting is over
+    system.spend_blocks((VOTING_PERIOD_LENGTH / 1000) as u32);
+    dao.comment(ADMIN, 1, 3, true);
+}
+
+#[test]
+fn vote_reasons() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let member = APPLICANTS[0];
+    let delegate: u64 = 300;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+    dao.update_delegate_key(member, delegate, false);
+    dao.submit_signaling_proposal(ADMIN, 1, 50, false);
+
+    // must fail since the text reason is limited
+    let reason = |length: usize| Some(VoteReason::Text("r".repeat(length)));
+    dao.submit_vote_with_reason(delegate, 1, Vote::No, reason(MAX_REASON_LENGTH + 1), true);
+    dao.submit_vote_with_reason(delegate, 1, Vote::No, reason(MAX_REASON_LENGTH), false);
+    dao.submit_vote_with_reason(ADMIN, 1, Vote::Yes, Some(VoteReason::Hash([1; 32])), false);
+}
+
+#[test]
+fn native_value_tribute() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let value_tribute: u128 = 1_000_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+    let quorum: u128 = 50;
+
+    system.mint_to(ADMIN, value_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+
+    // must fail since the tribute can't be paid both in tokens and in native value
+    let res = dao.send_with_value(
+        ADMIN,
+        DaoAction::SubmitMembershipProposal {
+            applicant: applicant.into(),
+            token_tribute,
+            shares_requested,
+            quorum,
+            metadata: ProposalMetadata::default(),
+            gate_token_id: None,
+        },
+        value_tribute,
+    );
+    assert!(res.main_failed());
+
+    let res = dao.send_with_value(
+        ADMIN,
+        DaoAction::SubmitMembershipProposal {
+            applicant: applicant.into(),
+            token_tribute: 0,
+            shares_requested,
+            quorum,
+            metadata: ProposalMetadata::default(),
+            gate_token_id: None,
+        },
+        value_tribute,
+    );
+    let reply = DaoEvent::SubmitMembershipProposal {
+        proposer: ADMIN.into(),
+        applicant: applicant.into(),
+        proposal_id: 0,
+        token_tribute: 0,
+        value_tribute,
+    }
+    .encode();
+    assert!(res.contains(&(ADMIN, reply)));
+    assert_eq!(system.balance_of(ADMIN), 0);
+
+    dao.submit_vote(ADMIN, 0, Vote::No, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+
+    // proposal didn't pass
+    dao.process_proposal(0, false, false);
+
+    // the native value tribute is returned to the proposer
+    system.claim_value_from_mailbox(ADMIN);
+    assert_eq!(system.balance_of(ADMIN), value_tribute);
+    ftoken.check_balance(applicant, token_tribute);
+}
+
+#[test]
+fn funding_reduces_balance() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let amount: u128 = 5_000;
+    let member = APPLICANTS[0];
+    let receiver: u64 = 300;
+
+    ftoken.mint(0, member, member, token_tribute);
+    ftoken.approve(1, member, DAO_ID, token_tribute);
+    dao.add_member(&system, 0, member, token_tribute, shares_requested);
+
+    dao.submit_funding_proposal(ADMIN, 1, receiver, amount, 0, false);
+    dao.submit_vote(member, 1, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(1, true, false);
+    ftoken.check_balance(receiver, amount);
+
+    // the ragequit payout comes from the tokens left after the funding
+    let funds = (token_tribute - amount) * shares_requested / (shares_requested + 1);
+    dao.ragequit(member, shares_requested, funds, false);
+    ftoken.check_balance(member, funds);
+}
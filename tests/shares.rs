@@ -0,0 +1,161 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use ft_io::{FTAction, FTEvent};
+use gstd::{prelude::*, ActorId};
+use gtest::{Program, System};
+
+fn pass_governance_proposal(
+    system: &System,
+    dao: &Program,
+    proposal_id: u128,
+    action: GovernanceAction,
+) {
+    dao.submit_governance_proposal(ADMIN, proposal_id, action, 50, false);
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+}
+
+#[test]
+fn share_transfers() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let buyer: u64 = 300;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    // must fail since share transfers are disabled by default
+    dao.transfer_shares(APPLICANTS[0], APPLICANTS[1], 1_000, true);
+
+    pass_governance_proposal(
+        &system,
+        &dao,
+        proposal_id,
+        GovernanceAction::SetShareTransferPolicy(ShareTransferPolicy::MembersOnly),
+    );
+    proposal_id += 1;
+
+    dao.transfer_shares(APPLICANTS[0], APPLICANTS[1], 1_000, false);
+
+    // must fail since the buyer is not a member
+    dao.transfer_shares(APPLICANTS[0], buyer, 1_000, true);
+
+    // must fail since the member has unsufficient shares
+    dao.transfer_shares(APPLICANTS[0], APPLICANTS[1], shares_requested, true);
+
+    // the buyer's address is used as the delegate key of another member
+    dao.update_delegate_key(APPLICANTS[2], buyer, false);
+
+    pass_governance_proposal(
+        &system,
+        &dao,
+        proposal_id,
+        GovernanceAction::SetShareTransferPolicy(ShareTransferPolicy::Whitelisted),
+    );
+    proposal_id += 1;
+
+    // must fail since the buyer is not in the whitelist
+    dao.transfer_shares(APPLICANTS[0], buyer, 1_000, true);
+
+    dao.add_to_whitelist(ADMIN, buyer, false);
+    dao.submit_funding_proposal(ADMIN, proposal_id, buyer, 0, 0, false);
+    dao.submit_vote(APPLICANTS[0], proposal_id, Vote::Yes, false);
+    dao.submit_vote(APPLICANTS[1], proposal_id, Vote::No, false);
+
+    // must fail since the proposal the member voted YES on is not processed
+    dao.transfer_shares(APPLICANTS[0], buyer, 1_000, true);
+    // must fail since the recipient could vote again with the shares voted NO
+    dao.transfer_shares(APPLICANTS[1], APPLICANTS[3], 1_000, true);
+
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, false, false);
+    proposal_id += 1;
+
+    dao.transfer_shares(APPLICANTS[1], APPLICANTS[3], 1_000, false);
+
+    dao.transfer_shares(APPLICANTS[0], buyer, 1_000, false);
+
+    // the buyer votes by its own address and the other member gets its own delegate key back
+    dao.submit_funding_proposal(buyer, proposal_id, buyer, 0, 0, false);
+    dao.submit_vote(buyer, proposal_id, Vote::Yes, false);
+    dao.submit_vote(APPLICANTS[2], proposal_id, Vote::Yes, false);
+}
+
+#[test]
+fn shares_as_fungible_token() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let applicant: u64 = 200;
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+    dao.submit_vote(ADMIN, 0, Vote::Yes, false);
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+
+    // the shares are minted to the applicant
+    let res = dao.send(ADMIN, DaoAction::ProcessProposal(0));
+    let transfer = FTEvent::Transfer {
+        from: ActorId::zero(),
+        to: applicant.into(),
+        amount: shares_requested,
+    };
+    assert!(res.contains(&(0, transfer.encode())));
+
+    // the token tooling sends the standard queries and decodes the standard replies
+    let res = dao.send(applicant, FTAction::BalanceOf(applicant.into()));
+    assert!(res.log().iter().any(|log| matches!(
+        FTEvent::decode(&mut log.payload()),
+        Ok(FTEvent::Balance(balance)) if balance == shares_requested
+    )));
+    let res = dao.send(applicant, FTAction::TotalSupply);
+    assert!(res.log().iter().any(|log| matches!(
+        FTEvent::decode(&mut log.payload()),
+        Ok(FTEvent::TotalSupply(total_supply)) if total_supply == shares_requested + 1
+    )));
+
+    // the shares are burned on ragequit
+    let res = dao.send(applicant, DaoAction::RageQuit(shares_requested));
+    let transfer = FTEvent::Transfer {
+        from: applicant.into(),
+        to: ActorId::zero(),
+        amount: shares_requested,
+    };
+    assert!(res.contains(&(0, transfer.encode())));
+    let res = dao.send(applicant, FTAction::BalanceOf(applicant.into()));
+    assert!(res.contains(&(applicant, FTEvent::Balance(0).encode())));
+}
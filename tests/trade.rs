@@ -0,0 +1,153 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gtest::{Program, System};
+
+// the program created right after the DAO
+const PAYMENT_TOKEN_ID: u64 = 3;
+
+#[test]
+fn trade_proposals() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let payment_token = Program::ftoken(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let trader: u64 = 200;
+    let contractor: u64 = 201;
+    let mut balance = 10 * token_tribute;
+    let mut proposal_id: u128 = 0;
+
+    // add members to DAO
+    for applicant in APPLICANTS {
+        ftoken.mint(0, *applicant, *applicant, token_tribute);
+        ftoken.approve(1, *applicant, DAO_ID, token_tribute);
+        dao.add_member(
+            &system,
+            proposal_id,
+            *applicant,
+            token_tribute,
+            shares_requested,
+        );
+        proposal_id += 1;
+    }
+
+    // must fail since the proposal neither offers nor requests anything
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        trader,
+        (PAYMENT_TOKEN_ID, 0),
+        (TOKEN_ID, 0),
+        0,
+        true,
+    );
+
+    // must fail since the trader requesting shares is not in the whitelist
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        trader,
+        (PAYMENT_TOKEN_ID, 0),
+        (TOKEN_ID, 0),
+        1_000,
+        true,
+    );
+
+    // the trader sells the payment tokens to the DAO
+    payment_token.mint(0, trader, trader, 5_000);
+    payment_token.approve(1, trader, DAO_ID, 5_000);
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        trader,
+        (PAYMENT_TOKEN_ID, 5_000),
+        (TOKEN_ID, 4_000),
+        0,
+        false,
+    );
+    // the tribute is held by the DAO
+    payment_token.check_balance(trader, 0);
+    payment_token.check_balance(DAO_ID, 5_000);
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    balance -= 4_000;
+    ftoken.check_balance(trader, 4_000);
+    ftoken.check_balance(DAO_ID, balance);
+
+    // the contractor buys in and is paid in the payment tokens
+    proposal_id += 1;
+    dao.add_to_whitelist(ADMIN, contractor, false);
+    ftoken.mint(0, contractor, contractor, 1_000);
+    ftoken.approve(1, contractor, DAO_ID, 1_000);
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        contractor,
+        (TOKEN_ID, 1_000),
+        (PAYMENT_TOKEN_ID, 3_000),
+        1_000,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, true, false);
+    balance += 1_000;
+    payment_token.check_balance(contractor, 3_000);
+    payment_token.check_balance(DAO_ID, 2_000);
+    ftoken.check_balance(DAO_ID, balance);
+
+    // the contractor has become a member and can submit proposals
+    proposal_id += 1;
+    dao.submit_funding_proposal(contractor, proposal_id, contractor, 0, 0, false);
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, false, false);
+
+    // the trade fails since the DAO doesn't have enough payment tokens
+    proposal_id += 1;
+    ftoken.mint(2, contractor, contractor, 1_000);
+    ftoken.approve(3, contractor, DAO_ID, 1_000);
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        contractor,
+        (TOKEN_ID, 1_000),
+        (PAYMENT_TOKEN_ID, 3_000),
+        0,
+        false,
+    );
+    for applicant in APPLICANTS {
+        dao.submit_vote(*applicant, proposal_id, Vote::Yes, false);
+    }
+    system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+    dao.process_proposal(proposal_id, false, false);
+
+    // the tribute is returned
+    ftoken.check_balance(contractor, 1_000);
+    ftoken.check_balance(DAO_ID, balance);
+
+    // the trader aborts the trade and gets the tribute back
+    proposal_id += 1;
+    payment_token.mint(2, trader, trader, 1_000);
+    payment_token.approve(3, trader, DAO_ID, 1_000);
+    dao.submit_trade_proposal(
+        ADMIN,
+        proposal_id,
+        trader,
+        (PAYMENT_TOKEN_ID, 1_000),
+        (TOKEN_ID, 1_000),
+        0,
+        false,
+    );
+    payment_token.check_balance(trader, 0);
+    dao.abort(trader, proposal_id, false);
+    payment_token.check_balance(trader, 1_000);
+}
@@ -1,20 +1,28 @@
-use dao_io::{DaoAction, DaoEvent, InitDao, Vote};
+use dao_io::{
+    DaoAction, DaoEvent, GovernanceAction, GrantSchedule, InitDao, MembershipGate,
+    ProposalMetadata, ProposalOption, Role, Vote, VoteReason, WhitelistUpdate,
+};
 use ft_main_io::{FTokenAction, FTokenEvent, InitFToken, LogicAction};
+use gear_lib::non_fungible_token::token::TokenMetadata;
+use nft_io::{Collection, Constraints, InitNFT, NFTAction, NFTEvent};
+use primitive_types::U256;
 
-use gstd::prelude::*;
+use gstd::{prelude::*, ActorId};
 use gtest::{Program, System};
 pub const ADMIN: u64 = 100;
-const TOKEN_ID: u64 = 1;
+pub const TOKEN_ID: u64 = 1;
 pub const DAO_ID: u64 = 2;
 pub const PERIOD_DURATION: u64 = 10000000;
 pub const VOTING_PERIOD_LENGTH: u64 = 100000000;
 pub const GRACE_PERIOD_LENGTH: u64 = 10000000;
 const DILUTION_BOUND: u8 = 3;
 pub const ABORT_WINDOW: u64 = 10000000;
+pub const ADMIN_OPERATION_LIFETIME: u64 = 10000000;
 pub const APPLICANTS: &[u64] = &[10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
 
 pub trait Dao {
     fn dao(system: &System) -> Program;
+    fn dao_with_membership_nft(system: &System, membership_nft: u64) -> Program;
     fn add_to_whitelist(&self, from: u64, account: u64, error: bool);
     #[allow(clippy::too_many_arguments)]
     fn submit_membership_proposal(
@@ -38,9 +46,122 @@ pub trait Dao {
     );
     fn process_proposal(&self, proposal_id: u128, passed: bool, error: bool);
     fn submit_vote(&self, from: u64, proposal_id: u128, vote: Vote, error: bool);
+    fn submit_vote_with_reason(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        vote: Vote,
+        reason: Option<VoteReason>,
+        error: bool,
+    );
+    fn submit_ranked_vote(&self, from: u64, proposal_id: u128, ranking: Vec<u32>, error: bool);
+    fn close_voting(&self, from: u64, proposal_id: u128, error: bool);
+    fn comment(&self, from: u64, proposal_id: u128, comment_index: u32, error: bool);
     fn ragequit(&self, from: u64, amount: u128, funds: u128, error: bool);
     fn abort(&self, from: u64, proposal_id: u128, error: bool);
+    fn cancel_proposal(&self, from: u64, proposal_id: u128, error: bool);
     fn update_delegate_key(&self, from: u64, account: u64, error: bool);
+    fn transfer_shares(&self, from: u64, to: u64, amount: u128, error: bool);
+    fn submit_conviction_request(
+        &self,
+        from: u64,
+        request_id: u128,
+        applicant: u64,
+        amount: u128,
+        error: bool,
+    );
+    fn stake_conviction(&self, from: u64, request_id: u128, amount: u128, error: bool);
+    fn withdraw_conviction(&self, from: u64, request_id: u128, amount: u128, error: bool);
+    fn execute_conviction_request(
+        &self,
+        from: u64,
+        request_id: u128,
+        applicant: u64,
+        amount: u128,
+        error: bool,
+    );
+    fn set_admin_signers(&self, from: u64, signers: &[u64], threshold: u32, error: bool);
+    fn propose_admin_operation(&self, from: u64, action: DaoAction, operation_id: u64, error: bool);
+    fn confirm_admin_operation(&self, from: u64, operation_id: u64, reply: DaoEvent, error: bool);
+    fn grant_role(&self, from: u64, account: u64, role: Role, error: bool);
+    fn pause(&self, from: u64, error: bool);
+    fn treasurer_payout(&self, from: u64, recipient: u64, amount: u128, error: bool);
+    fn propose_admin(&self, from: u64, account: u64, error: bool);
+    fn remove_from_whitelist(&self, from: u64, account: u64, error: bool);
+    fn update_whitelist(&self, from: u64, update: WhitelistUpdate, error: bool);
+    fn set_membership_gate(&self, from: u64, gate: Option<MembershipGate>, error: bool);
+    #[allow(clippy::too_many_arguments)]
+    fn submit_gated_membership_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        token_tribute: u128,
+        shares_requested: u128,
+        gate_token_id: Option<u64>,
+        error: bool,
+    );
+    fn submit_governance_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        action: GovernanceAction,
+        quorum: u128,
+        error: bool,
+    );
+    fn submit_ranked_choice_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        options: Vec<ProposalOption>,
+        quorum: u128,
+        error: bool,
+    );
+    fn submit_signaling_proposal(&self, from: u64, proposal_id: u128, quorum: u128, error: bool);
+    fn accept_admin(&self, from: u64, error: bool);
+    fn cancel_admin_transfer(&self, from: u64, account: u64, error: bool);
+    #[allow(clippy::too_many_arguments)]
+    fn submit_grant_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        amount: u128,
+        schedule: GrantSchedule,
+        quorum: u128,
+        error: bool,
+    );
+    fn claim_grant(&self, from: u64, grant_id: u128, recipient: u64, amount: u128, error: bool);
+    fn release_milestone(
+        &self,
+        from: u64,
+        grant_id: u128,
+        milestone: u32,
+        amount: u128,
+        error: bool,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn submit_trade_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        tribute: (u64, u128),
+        payment: (u64, u128),
+        shares_requested: u128,
+        error: bool,
+    );
+    #[allow(clippy::too_many_arguments)]
+    fn spend_budget(
+        &self,
+        from: u64,
+        token: u64,
+        recipient: u64,
+        amount: u128,
+        epoch: u64,
+        spent: u128,
+        error: bool,
+    );
     fn add_member(
         &self,
         system: &System,
@@ -51,24 +172,35 @@ pub trait Dao {
     );
 }
 
+fn init_dao(system: &System, membership_nft: Option<ActorId>) -> Program {
+    let dao = Program::current(system);
+    assert!(!dao
+        .send(
+            ADMIN,
+            InitDao {
+                admin: ADMIN.into(),
+                approved_token_program_id: TOKEN_ID.into(),
+                period_duration: PERIOD_DURATION,
+                voting_period_length: VOTING_PERIOD_LENGTH,
+                grace_period_length: GRACE_PERIOD_LENGTH,
+                dilution_bound: DILUTION_BOUND,
+                abort_window: ABORT_WINDOW,
+                admin_operation_lifetime: ADMIN_OPERATION_LIFETIME,
+                membership_nft,
+                optimistic: None,
+            },
+        )
+        .main_failed());
+    dao
+}
+
 impl Dao for Program<'_> {
     fn dao(system: &System) -> Program {
-        let dao = Program::current(system);
-        assert!(!dao
-            .send(
-                ADMIN,
-                InitDao {
-                    admin: ADMIN.into(),
-                    approved_token_program_id: TOKEN_ID.into(),
-                    period_duration: PERIOD_DURATION,
-                    voting_period_length: VOTING_PERIOD_LENGTH,
-                    grace_period_length: GRACE_PERIOD_LENGTH,
-                    dilution_bound: DILUTION_BOUND,
-                    abort_window: ABORT_WINDOW,
-                },
-            )
-            .main_failed());
-        dao
+        init_dao(system, None)
+    }
+
+    fn dao_with_membership_nft(system: &System, membership_nft: u64) -> Program {
+        init_dao(system, Some(membership_nft.into()))
     }
 
     fn add_to_whitelist(&self, from: u64, account: u64, error: bool) {
@@ -98,7 +230,8 @@ impl Dao for Program<'_> {
                 token_tribute,
                 shares_requested,
                 quorum,
-                details: String::from(""),
+                metadata: ProposalMetadata::default(),
+                gate_token_id: None,
             },
         );
         let reply = DaoEvent::SubmitMembershipProposal {
@@ -106,6 +239,7 @@ impl Dao for Program<'_> {
             applicant: applicant.into(),
             proposal_id,
             token_tribute,
+            value_tribute: 0,
         }
         .encode();
         if error {
@@ -128,8 +262,10 @@ impl Dao for Program<'_> {
             DaoAction::SubmitFundingProposal {
                 applicant: applicant.into(),
                 amount,
+                value_amount: 0,
                 quorum,
-                details: String::from(""),
+                metadata: ProposalMetadata::default(),
+                optimistic: false,
             },
         );
         let reply = DaoEvent::SubmitFundingProposal {
@@ -137,6 +273,7 @@ impl Dao for Program<'_> {
             applicant: applicant.into(),
             proposal_id,
             amount,
+            value_amount: 0,
         }
         .encode();
         if error {
@@ -160,17 +297,82 @@ impl Dao for Program<'_> {
         }
     }
     fn submit_vote(&self, from: u64, proposal_id: u128, vote: Vote, error: bool) {
+        self.submit_vote_with_reason(from, proposal_id, vote, None, error);
+    }
+    fn submit_vote_with_reason(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        vote: Vote,
+        reason: Option<VoteReason>,
+        error: bool,
+    ) {
         let res = self.send(
             from,
             DaoAction::SubmitVote {
                 proposal_id,
                 vote: vote.clone(),
+                reason: reason.clone(),
             },
         );
         let reply = DaoEvent::SubmitVote {
             account: from.into(),
             proposal_id,
             vote,
+            reason,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+    fn submit_ranked_vote(&self, from: u64, proposal_id: u128, ranking: Vec<u32>, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitRankedVote {
+                proposal_id,
+                ranking: ranking.clone(),
+            },
+        );
+        let reply = DaoEvent::SubmitRankedVote {
+            account: from.into(),
+            proposal_id,
+            ranking,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+    fn close_voting(&self, from: u64, proposal_id: u128, error: bool) {
+        let res = self.send(from, DaoAction::CloseVoting(proposal_id));
+        if error {
+            assert!(res.main_failed());
+        } else {
+            // the voting is closed at the current block timestamp
+            assert!(res.log().iter().any(|log| matches!(
+                DaoEvent::decode(&mut log.payload()),
+                Ok(DaoEvent::VotingClosed { proposal_id: id, .. }) if id == proposal_id
+            )));
+        }
+    }
+    fn comment(&self, from: u64, proposal_id: u128, comment_index: u32, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::Comment {
+                proposal_id,
+                content_hash: [1; 32],
+            },
+        );
+        let reply = DaoEvent::Commented {
+            proposal_id,
+            comment_index,
+            author: from.into(),
+            content_hash: [1; 32],
         }
         .encode();
         if error {
@@ -184,6 +386,7 @@ impl Dao for Program<'_> {
         let reply = DaoEvent::RageQuit {
             member: from.into(),
             amount: funds,
+            value: 0,
         }
         .encode();
         if error {
@@ -201,6 +404,15 @@ impl Dao for Program<'_> {
             assert!(res.contains(&(from, reply)));
         }
     }
+    fn cancel_proposal(&self, from: u64, proposal_id: u128, error: bool) {
+        let res = self.send(from, DaoAction::CancelProposal(proposal_id));
+        let reply = DaoEvent::CancelProposal(proposal_id).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
     fn update_delegate_key(&self, from: u64, account: u64, error: bool) {
         let res = self.send(from, DaoAction::UpdateDelegateKey(account.into()));
         let reply = DaoEvent::DelegateKeyUpdated {
@@ -215,27 +427,621 @@ impl Dao for Program<'_> {
         }
     }
 
-    fn add_member(
+    fn submit_conviction_request(
         &self,
-        system: &System,
-        proposal_id: u128,
+        from: u64,
+        request_id: u128,
         applicant: u64,
-        token_tribute: u128,
-        shares_requested: u128,
+        amount: u128,
+        error: bool,
     ) {
-        self.add_to_whitelist(ADMIN, applicant, false);
-        self.submit_membership_proposal(
-            ADMIN,
-            proposal_id,
-            applicant,
-            token_tribute,
-            shares_requested,
-            0,
-            false,
+        let res = self.send(
+            from,
+            DaoAction::SubmitConvictionRequest {
+                applicant: applicant.into(),
+                amount,
+                metadata: ProposalMetadata::default(),
+            },
         );
-        self.submit_vote(ADMIN, proposal_id, Vote::Yes, false);
-        system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
-        self.process_proposal(proposal_id, true, false);
+        let reply = DaoEvent::ConvictionRequestSubmitted {
+            proposer: from.into(),
+            applicant: applicant.into(),
+            request_id,
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn stake_conviction(&self, from: u64, request_id: u128, amount: u128, error: bool) {
+        let res = self.send(from, DaoAction::StakeConviction { request_id, amount });
+        let reply = DaoEvent::ConvictionStaked {
+            member: from.into(),
+            request_id,
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn withdraw_conviction(&self, from: u64, request_id: u128, amount: u128, error: bool) {
+        let res = self.send(from, DaoAction::WithdrawConviction { request_id, amount });
+        let reply = DaoEvent::ConvictionWithdrawn {
+            member: from.into(),
+            request_id,
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn execute_conviction_request(
+        &self,
+        from: u64,
+        request_id: u128,
+        applicant: u64,
+        amount: u128,
+        error: bool,
+    ) {
+        let res = self.send(from, DaoAction::ExecuteConvictionRequest(request_id));
+        let reply = DaoEvent::ConvictionRequestExecuted {
+            request_id,
+            applicant: applicant.into(),
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn transfer_shares(&self, from: u64, to: u64, amount: u128, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::TransferShares {
+                to: to.into(),
+                amount,
+            },
+        );
+        let reply = DaoEvent::SharesTransferred {
+            from: from.into(),
+            to: to.into(),
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn set_admin_signers(&self, from: u64, signers: &[u64], threshold: u32, error: bool) {
+        let signers: Vec<ActorId> = signers.iter().map(|signer| (*signer).into()).collect();
+        let res = self.send(
+            from,
+            DaoAction::SetAdminSigners {
+                signers: signers.clone(),
+                threshold,
+            },
+        );
+        let reply = DaoEvent::AdminSignersUpdated { signers, threshold }.encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn propose_admin_operation(
+        &self,
+        from: u64,
+        action: DaoAction,
+        operation_id: u64,
+        error: bool,
+    ) {
+        let res = self.send(from, action);
+        let reply = DaoEvent::AdminOperationProposed {
+            operation_id,
+            proposer: from.into(),
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn confirm_admin_operation(&self, from: u64, operation_id: u64, reply: DaoEvent, error: bool) {
+        let res = self.send(from, DaoAction::ConfirmAdminOperation(operation_id));
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply.encode())));
+        }
+    }
+
+    fn grant_role(&self, from: u64, account: u64, role: Role, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::GrantRole {
+                account: account.into(),
+                role,
+            },
+        );
+        let reply = DaoEvent::RoleGranted {
+            account: account.into(),
+            role,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn pause(&self, from: u64, error: bool) {
+        let res = self.send(from, DaoAction::Pause);
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, DaoEvent::Paused.encode())));
+        }
+    }
+
+    fn treasurer_payout(&self, from: u64, recipient: u64, amount: u128, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::TreasurerPayout {
+                recipient: recipient.into(),
+                amount,
+            },
+        );
+        let reply = DaoEvent::TreasurerPayout {
+            treasurer: from.into(),
+            recipient: recipient.into(),
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn remove_from_whitelist(&self, from: u64, account: u64, error: bool) {
+        let res = self.send(from, DaoAction::RemoveFromWhitelist(account.into()));
+        let reply = DaoEvent::MemberRemovedFromWhitelist(account.into()).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn update_whitelist(&self, from: u64, update: WhitelistUpdate, error: bool) {
+        let res = self.send(from, DaoAction::UpdateWhitelist(update.clone()));
+        let reply = DaoEvent::WhitelistUpdated {
+            added: update.add,
+            removed: update.remove,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn set_membership_gate(&self, from: u64, gate: Option<MembershipGate>, error: bool) {
+        let res = self.send(from, DaoAction::SetMembershipGate(gate));
+        let reply = DaoEvent::MembershipGateUpdated(gate).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_gated_membership_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        token_tribute: u128,
+        shares_requested: u128,
+        gate_token_id: Option<u64>,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitMembershipProposal {
+                applicant: applicant.into(),
+                token_tribute,
+                shares_requested,
+                quorum: 0,
+                metadata: ProposalMetadata::default(),
+                gate_token_id: gate_token_id.map(|token_id| U256::from(token_id).0),
+            },
+        );
+        if error {
+            // the applicant rejected by the gate is reported as a failed transaction
+            assert!(res.log().iter().any(|log| matches!(
+                DaoEvent::decode(&mut log.payload()),
+                Ok(DaoEvent::TransactionFailed(_))
+            )));
+        } else {
+            let reply = DaoEvent::SubmitMembershipProposal {
+                proposer: from.into(),
+                applicant: applicant.into(),
+                proposal_id,
+                token_tribute,
+                value_tribute: 0,
+            }
+            .encode();
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_governance_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        action: GovernanceAction,
+        quorum: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitGovernanceProposal {
+                action,
+                quorum,
+                metadata: ProposalMetadata::default(),
+            },
+        );
+        let reply = DaoEvent::SubmitGovernanceProposal {
+            proposer: from.into(),
+            proposal_id,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_ranked_choice_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        options: Vec<ProposalOption>,
+        quorum: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitRankedChoiceProposal {
+                options,
+                quorum,
+                metadata: ProposalMetadata::default(),
+            },
+        );
+        let reply = DaoEvent::SubmitRankedChoiceProposal {
+            proposer: from.into(),
+            proposal_id,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_signaling_proposal(&self, from: u64, proposal_id: u128, quorum: u128, error: bool) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitSignalingProposal {
+                quorum,
+                metadata: ProposalMetadata {
+                    content_hash: [1; 32],
+                    ..ProposalMetadata::default()
+                },
+            },
+        );
+        let reply = DaoEvent::SubmitSignalingProposal {
+            proposer: from.into(),
+            proposal_id,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn propose_admin(&self, from: u64, account: u64, error: bool) {
+        let res = self.send(from, DaoAction::ProposeAdmin(account.into()));
+        let reply = DaoEvent::AdminTransferProposed(account.into()).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn accept_admin(&self, from: u64, error: bool) {
+        let res = self.send(from, DaoAction::AcceptAdmin);
+        let reply = DaoEvent::AdminUpdated(from.into()).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn cancel_admin_transfer(&self, from: u64, account: u64, error: bool) {
+        let res = self.send(from, DaoAction::CancelAdminTransfer);
+        let reply = DaoEvent::AdminTransferCancelled(account.into()).encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_grant_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        amount: u128,
+        schedule: GrantSchedule,
+        quorum: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitGrantProposal {
+                applicant: applicant.into(),
+                amount,
+                schedule,
+                quorum,
+                metadata: ProposalMetadata::default(),
+                optimistic: false,
+            },
+        );
+        let reply = DaoEvent::SubmitGrantProposal {
+            proposer: from.into(),
+            applicant: applicant.into(),
+            proposal_id,
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn claim_grant(&self, from: u64, grant_id: u128, recipient: u64, amount: u128, error: bool) {
+        let res = self.send(from, DaoAction::ClaimGrant(grant_id));
+        let reply = DaoEvent::GrantClaimed {
+            grant_id,
+            recipient: recipient.into(),
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn release_milestone(
+        &self,
+        from: u64,
+        grant_id: u128,
+        milestone: u32,
+        amount: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::ReleaseMilestone {
+                grant_id,
+                milestone,
+            },
+        );
+        let reply = DaoEvent::MilestoneReleased {
+            grant_id,
+            milestone,
+            amount,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn submit_trade_proposal(
+        &self,
+        from: u64,
+        proposal_id: u128,
+        applicant: u64,
+        (tribute_token, tribute_offered): (u64, u128),
+        (payment_token, payment_requested): (u64, u128),
+        shares_requested: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SubmitTradeProposal {
+                applicant: applicant.into(),
+                tribute_token: tribute_token.into(),
+                tribute_offered,
+                payment_token: payment_token.into(),
+                payment_requested,
+                shares_requested,
+                quorum: 0,
+                metadata: ProposalMetadata::default(),
+            },
+        );
+        let reply = DaoEvent::SubmitTradeProposal {
+            proposer: from.into(),
+            applicant: applicant.into(),
+            proposal_id,
+            tribute_offered,
+            payment_requested,
+            shares_requested,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn spend_budget(
+        &self,
+        from: u64,
+        token: u64,
+        recipient: u64,
+        amount: u128,
+        epoch: u64,
+        spent: u128,
+        error: bool,
+    ) {
+        let res = self.send(
+            from,
+            DaoAction::SpendBudget {
+                token: token.into(),
+                recipient: recipient.into(),
+                amount,
+            },
+        );
+        let reply = DaoEvent::BudgetSpent {
+            treasurer: from.into(),
+            token: token.into(),
+            recipient: recipient.into(),
+            amount,
+            epoch,
+            spent,
+        }
+        .encode();
+        if error {
+            assert!(res.main_failed());
+        } else {
+            assert!(res.contains(&(from, reply)));
+        }
+    }
+
+    fn add_member(
+        &self,
+        system: &System,
+        proposal_id: u128,
+        applicant: u64,
+        token_tribute: u128,
+        shares_requested: u128,
+    ) {
+        self.add_to_whitelist(ADMIN, applicant, false);
+        self.submit_membership_proposal(
+            ADMIN,
+            proposal_id,
+            applicant,
+            token_tribute,
+            shares_requested,
+            0,
+            false,
+        );
+        self.submit_vote(ADMIN, proposal_id, Vote::Yes, false);
+        system.spend_blocks(VOTING_PERIOD_LENGTH as u32 + 1);
+        self.process_proposal(proposal_id, true, false);
+    }
+}
+
+pub trait Nft {
+    fn nft(system: &System, minter: u64) -> Program;
+    fn mint(&self, transaction_id: u64, from: u64);
+    fn approve(&self, transaction_id: u64, from: u64, to: u64, token_id: u64);
+    fn check_owner(&self, token_id: u64, owner: u64);
+}
+
+impl Nft for Program<'_> {
+    fn nft(system: &System, minter: u64) -> Program {
+        let nft = Program::from_file(system, "./target/wasm32-unknown-unknown/debug/nft.opt.wasm");
+        let res = nft.send(
+            ADMIN,
+            InitNFT {
+                collection: Collection::default(),
+                royalties: None,
+                constraints: Constraints {
+                    max_mint_count: None,
+                    authorized_minters: vec![minter.into()],
+                },
+            },
+        );
+        assert!(!res.main_failed());
+        nft
+    }
+
+    fn mint(&self, transaction_id: u64, from: u64) {
+        let res = self.send(
+            from,
+            NFTAction::Mint {
+                transaction_id,
+                token_metadata: TokenMetadata::default(),
+            },
+        );
+        assert!(!res.main_failed());
+    }
+
+    fn approve(&self, transaction_id: u64, from: u64, to: u64, token_id: u64) {
+        let res = self.send(
+            from,
+            NFTAction::Approve {
+                transaction_id,
+                to: to.into(),
+                token_id: U256::from(token_id),
+            },
+        );
+        assert!(!res.main_failed());
+    }
+
+    // the burned token is owned by the zero address
+    fn check_owner(&self, token_id: u64, owner: u64) {
+        let token_id = U256::from(token_id);
+        let res = self.send(ADMIN, NFTAction::Owner { token_id });
+        let reply = NFTEvent::Owner {
+            owner: owner.into(),
+            token_id,
+        }
+        .encode();
+        assert!(res.contains(&(ADMIN, reply)));
     }
 }
 
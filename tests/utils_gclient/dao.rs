@@ -1,7 +1,7 @@
 #![allow(unused)]
 
 use super::common;
-use dao_io::{DaoAction, DaoEvent, InitDao, Vote};
+use dao_io::{DaoAction, DaoEvent, InitDao, ProposalMetadata, Vote};
 use gclient::{EventProcessor, GearApi};
 use gstd::{prelude::*, ActorId};
 
@@ -15,6 +15,7 @@ pub async fn init(
     grace_period_length: u64,
     dilution_bound: u8,
     abort_window: u64,
+    admin_operation_lifetime: u64,
 ) -> gclient::Result<ActorId> {
     let mut listener = api.subscribe().await?;
     assert!(listener.blocks_running().await?);
@@ -27,6 +28,9 @@ pub async fn init(
         grace_period_length,
         dilution_bound,
         abort_window,
+        admin_operation_lifetime,
+        membership_nft: None,
+        optimistic: None,
     }
     .encode();
 
@@ -88,7 +92,7 @@ pub async fn submit_membership_proposal(
     token_tribute: u128,
     shares_requested: u128,
     quorum: u128,
-    details: &str,
+    title: &str,
     should_fail: bool,
 ) -> gclient::Result<Option<u128>> {
     let reply = send_message(
@@ -99,13 +103,17 @@ pub async fn submit_membership_proposal(
             token_tribute,
             shares_requested,
             quorum,
-            details: details.to_owned(),
+            metadata: ProposalMetadata {
+                title: title.to_owned(),
+                ..Default::default()
+            },
+            gate_token_id: None,
         },
     )
     .await?;
 
     if !should_fail {
-        let DaoEvent::SubmitMembershipProposal { proposer: _, applicant: _, proposal_id, token_tribute: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+        let DaoEvent::SubmitMembershipProposal { proposer: _, applicant: _, proposal_id, token_tribute: _, value_tribute: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
             panic!("Unexpected invalid `DaoEvent`.");
         };
 
@@ -125,7 +133,7 @@ pub async fn submit_funding_proposal(
     applicant: &ActorId,
     amount: u128,
     quorum: u128,
-    details: &str,
+    title: &str,
     should_fail: bool,
 ) -> gclient::Result<()> {
     let reply = send_message(
@@ -134,14 +142,19 @@ pub async fn submit_funding_proposal(
         DaoAction::SubmitFundingProposal {
             applicant: *applicant,
             amount,
+            value_amount: 0,
             quorum,
-            details: details.to_owned(),
+            metadata: ProposalMetadata {
+                title: title.to_owned(),
+                ..Default::default()
+            },
+            optimistic: false,
         },
     )
     .await?;
 
     if !should_fail {
-        let DaoEvent::SubmitFundingProposal { proposer: _, amount: _, applicant: _, proposal_id: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+        let DaoEvent::SubmitFundingProposal { proposer: _, amount: _, value_amount: _, applicant: _, proposal_id: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
             panic!("Unexpected invalid `DaoEvent`.");
         };
     } else {
@@ -181,10 +194,19 @@ pub async fn submit_vote(
     vote: Vote,
     should_fail: bool,
 ) -> gclient::Result<()> {
-    let reply = send_message(api, program_id, DaoAction::SubmitVote { proposal_id, vote }).await?;
+    let reply = send_message(
+        api,
+        program_id,
+        DaoAction::SubmitVote {
+            proposal_id,
+            vote,
+            reason: None,
+        },
+    )
+    .await?;
 
     if !should_fail {
-        let DaoEvent::SubmitVote { account: _, proposal_id: _, vote: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+        let DaoEvent::SubmitVote { account: _, proposal_id: _, vote: _, reason: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
             panic!("Unexpected invalid `DaoEvent`.");
         };
     } else {
@@ -205,7 +227,7 @@ pub async fn rage_quit(
     let reply = send_message(api, program_id, DaoAction::RageQuit(shares_amount)).await?;
 
     if !should_fail {
-        let DaoEvent::RageQuit { member: _, amount: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+        let DaoEvent::RageQuit { member: _, amount: _, value: _ } = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
             panic!("Unexpected invalid `DaoEvent`.");
         };
     } else {
@@ -259,13 +281,33 @@ pub async fn update_delegate_key(
     Ok(())
 }
 
-pub async fn set_admin(
+pub async fn propose_admin(
     api: &GearApi,
     program_id: &ActorId,
     new_admin: &ActorId,
     should_fail: bool,
 ) -> gclient::Result<()> {
-    let reply = send_message(api, program_id, DaoAction::SetAdmin(*new_admin)).await?;
+    let reply = send_message(api, program_id, DaoAction::ProposeAdmin(*new_admin)).await?;
+
+    if !should_fail {
+        let DaoEvent::AdminTransferProposed(_) = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+            panic!("Unexpected invalid `DaoEvent`.");
+        };
+    } else {
+        let DaoEvent::TransactionFailed(_) = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
+            panic!("Unexpected invalid `DaoEvent`.");
+        };
+    }
+
+    Ok(())
+}
+
+pub async fn accept_admin(
+    api: &GearApi,
+    program_id: &ActorId,
+    should_fail: bool,
+) -> gclient::Result<()> {
+    let reply = send_message(api, program_id, DaoAction::AcceptAdmin).await?;
 
     if !should_fail {
         let DaoEvent::AdminUpdated(_) = DaoEvent::decode(&mut reply.as_ref()).expect("Unexpected invalid `DaoEvent` data.") else {
@@ -0,0 +1,231 @@
+pub mod utils;
+
+use crate::utils::*;
+use dao_io::*;
+use gstd::prelude::*;
+use gtest::{Program, System};
+use primitive_types::U256;
+
+#[test]
+fn whitelist_updates() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+    let temporary_applicant: u64 = 201;
+
+    // must fail since the member is not in the whitelist
+    dao.remove_from_whitelist(ADMIN, applicant, true);
+
+    dao.add_to_whitelist(ADMIN, applicant, false);
+    dao.remove_from_whitelist(ADMIN, applicant, false);
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+
+    // must fail since the applicant has been removed from the whitelist
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        true,
+    );
+
+    // must fail since the update is empty
+    dao.update_whitelist(ADMIN, WhitelistUpdate::default(), true);
+
+    dao.update_whitelist(
+        ADMIN,
+        WhitelistUpdate {
+            add: vec![temporary_applicant.into()],
+            expires_at: Some(system.block_timestamp() + PERIOD_DURATION),
+            remove: vec![],
+        },
+        false,
+    );
+    system.spend_blocks((PERIOD_DURATION / 1000) as u32 + 1);
+
+    ftoken.mint(2, temporary_applicant, temporary_applicant, token_tribute);
+    ftoken.approve(3, temporary_applicant, DAO_ID, token_tribute);
+
+    // must fail since the whitelist entry has expired
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        temporary_applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        true,
+    );
+}
+
+#[test]
+fn whitelist_governed_by_proposals() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let applicant: u64 = 200;
+
+    let res = dao.send(
+        ADMIN,
+        DaoAction::SetWhitelistMode(WhitelistMode::Governance),
+    );
+    assert!(res.contains(&(
+        ADMIN,
+        DaoEvent::WhitelistModeUpdated(WhitelistMode::Governance).encode()
+    )));
+
+    // must fail since the whitelist can be changed only by proposals
+    dao.add_to_whitelist(ADMIN, applicant, true);
+
+    dao.submit_governance_proposal(
+        ADMIN,
+        0,
+        GovernanceAction::UpdateWhitelist(WhitelistUpdate {
+            add: vec![applicant.into()],
+            expires_at: None,
+            remove: vec![],
+        }),
+        50,
+        false,
+    );
+    dao.submit_vote(ADMIN, 0, Vote::Yes, false);
+    system.spend_blocks(((VOTING_PERIOD_LENGTH + GRACE_PERIOD_LENGTH) / 1000) as u32);
+    dao.process_proposal(0, true, false);
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+    dao.submit_membership_proposal(
+        ADMIN,
+        1,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+}
+
+#[test]
+fn token_gated_membership() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let min_balance: u128 = 20_000;
+    let applicant: u64 = 200;
+
+    let gate = Some(MembershipGate::Token {
+        program: TOKEN_ID.into(),
+        min_balance,
+    });
+    // must fail since only whitelisters or admin signers can set the gate
+    dao.set_membership_gate(applicant, gate, true);
+    dao.set_membership_gate(ADMIN, gate, false);
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+
+    // must fail since the applicant holds less than the gate requires
+    dao.submit_gated_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        None,
+        true,
+    );
+
+    ftoken.mint(2, applicant, applicant, min_balance - token_tribute);
+    dao.submit_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        0,
+        false,
+    );
+}
+
+#[test]
+fn nft_gated_membership() {
+    let system = System::new();
+    system.init_logger();
+    let ftoken = Program::ftoken(&system);
+    let dao = Program::dao(&system);
+    let applicant: u64 = 200;
+    let nft = Program::nft(&system, applicant);
+    let token_tribute: u128 = 10_000;
+    let shares_requested: u128 = 10_000;
+    let nft_id: u64 = 3;
+
+    dao.set_membership_gate(ADMIN, Some(MembershipGate::Nft(nft_id.into())), false);
+
+    ftoken.mint(0, applicant, applicant, token_tribute);
+    ftoken.approve(1, applicant, DAO_ID, token_tribute);
+
+    // must fail since the applicant doesn't own any token
+    dao.submit_gated_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        Some(0),
+        true,
+    );
+
+    nft.mint(0, applicant);
+
+    // must fail since the token isn't indicated
+    dao.submit_gated_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        None,
+        true,
+    );
+
+    // must fail since the native value tribute isn't accepted from the gated applicant
+    let value_tribute: u128 = 1_000_000;
+    system.mint_to(ADMIN, value_tribute);
+    let res = dao.send_with_value(
+        ADMIN,
+        DaoAction::SubmitMembershipProposal {
+            applicant: applicant.into(),
+            token_tribute: 0,
+            shares_requested,
+            quorum: 0,
+            metadata: ProposalMetadata::default(),
+            gate_token_id: Some(U256::from(0).0),
+        },
+        value_tribute,
+    );
+    assert!(res.main_failed());
+
+    dao.submit_gated_membership_proposal(
+        ADMIN,
+        0,
+        applicant,
+        token_tribute,
+        shares_requested,
+        Some(0),
+        false,
+    );
+}